@@ -0,0 +1,56 @@
+use std::{fs, path::Path, process::Command};
+
+/// Exports the output of `git describe --always --dirty` as `AOC_GIT_DESCRIBE`
+/// so that `aoc --version` can tell which commit the binary was built from.
+///
+/// If `git` is not installed or if we're not building from a git checkout
+/// (e.g. from a source tarball), the variable will simply not be set.
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    rerun_if_git_head_changed(Path::new("../.git"));
+
+    if let Some(describe) = git_describe() {
+        println!("cargo:rustc-env=AOC_GIT_DESCRIBE={describe}");
+    }
+}
+
+/// Makes cargo run this script again when a commit is checked out or created,
+/// or when the index changes. Does nothing if `git_dir` does not exist,
+/// because cargo would run this script on every build otherwise.
+fn rerun_if_git_head_changed(git_dir: &Path) {
+    let head = git_dir.join("HEAD");
+    let Ok(head_contents) = fs::read_to_string(&head) else {
+        return;
+    };
+
+    let mut paths = vec![head, git_dir.join("index")];
+    if let Some(reference) = head_contents.strip_prefix("ref: ") {
+        paths.push(git_dir.join(reference.trim()));
+    }
+
+    for path in paths
+        .iter()
+        .filter(|path| path.exists())
+    {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn git_describe() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let describe = String::from_utf8(output.stdout).ok()?;
+    let describe = describe.trim();
+    if describe.is_empty() {
+        return None;
+    }
+
+    Some(describe.to_owned())
+}
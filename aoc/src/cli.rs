@@ -1,17 +1,46 @@
-use crate::ident::{Filter, FilterTerm};
+use std::{path::PathBuf, sync::OnceLock};
+
+use crate::{
+    ident::{Day, Filter, FilterTerm, Id, Part, Year},
+    leaderboard::{Layout, SortKey},
+    solver::{SolveOptions, SummaryOrder},
+    ui::{Theme, UiConfig, ASCII_SPINNERS},
+};
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
 pub enum Command {
     Login,
     Logout,
-    Solve(Filter),
-    Stats(Filter),
+    Solve(Filter, SolveOptions, UiConfig),
+    Stats(Filter, Layout, Option<PathBuf>),
+    Submit(Id<(Year, Day, Part)>, String),
+    TestExamples(Filter),
+    Doctor,
+    CacheStats,
+    Reset(bool),
+    Where,
+}
+
+/// Options that affect all commands.
+#[derive(Debug, Clone, Default, PartialEq, Hash, Eq)]
+pub struct GlobalOptions {
+    /// Use the local instead of the roaming data directory on Windows.
+    pub local_data: bool,
 }
 
 #[derive(clap::Parser, Debug, Clone, PartialEq, Hash, Eq)]
+#[command(version = version())]
 struct CliArgs {
     #[command(subcommand)]
     command: Option<CliCommand>,
+
+    /// Keep data in `{FOLDERID_LocalAppData}` instead of
+    /// `{FOLDERID_RoamingAppData}` on Windows (has no effect elsewhere).
+    ///
+    /// You can also set the `AOC_USE_LOCAL_DATA` environment variable
+    /// to any value except `0`, `false`, or the empty string instead.
+    #[arg(long, global = true)]
+    local_data: bool,
 }
 
 /// Solve Advent of Code puzzles and print your personal leaderboard statistics.
@@ -42,7 +71,7 @@ enum CliCommand {
     /// the configuration directory of this program.
     Logout,
     /// Solve Advent of Code puzzles (default command).
-    Solve(Puzzles),
+    Solve(SolveArgs),
     /// Print your personal leaderboard statistics.
     ///
     /// To run this command, you'll have to copy and paste your
@@ -52,10 +81,46 @@ enum CliCommand {
     /// `$DATA_DIR` is `$XDG_DATA_HOME` or `$HOME/.local/share` on Linux,
     /// `$HOME/Library/Application Support` on macOS, and
     /// `{FOLDERID_RoamingAppData}` (i.e. `C:\Users\You\AppData\Roaming`)
-    /// on Windows.
+    /// on Windows (or `{FOLDERID_LocalAppData}` if `--local-data` is set).
     /// The files must be named `y21_personal_leaderboard_statistics.txt`
     /// for year 2021, for example.
-    Stats(Puzzles),
+    /// Alternatively, you can save the JSON of a private leaderboard you own
+    /// as `y21_personal_leaderboard_statistics.json`.
+    Stats(StatsArgs),
+    /// Submit an answer to adventofcode.com.
+    ///
+    /// Requires you to be logged in (see `login`).
+    /// Please note that adventofcode.com will make you wait
+    /// before you can submit another answer if your answer was wrong.
+    Submit(SubmitArgs),
+    /// Check the solvers against the example puzzle inputs.
+    ///
+    /// Runs each selected solver on those example puzzle inputs
+    /// for which the expected answers are known
+    /// and compares the answers of the solver to the expected ones.
+    /// This does not require your personal puzzle inputs.
+    TestExamples(TestExamplesArgs),
+    /// Check whether directories and session cookie are set up correctly.
+    ///
+    /// Prints the directories this program uses, whether they are writable,
+    /// whether you are logged in (see `login`),
+    /// and how many puzzle inputs and leaderboards have been saved.
+    /// Exits with a non-zero exit code if something is broken.
+    Doctor,
+    /// Print how many puzzle inputs are cached and how large they are.
+    CacheStats,
+    /// Delete all cached puzzle inputs.
+    ///
+    /// Your session cookie will be kept (see `logout`),
+    /// as will your personal leaderboard files and the puzzle inputs
+    /// in the directory set by `AOC_INPUT_DIR` (if any).
+    /// Requires `--yes` to actually delete anything.
+    Reset(ResetArgs),
+    /// Print the directories this program uses and the session cookie path.
+    ///
+    /// Prints one path per line, even if it does not exist (yet).
+    /// Use `doctor` to check whether everything is set up correctly.
+    Where,
 }
 
 #[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
@@ -76,34 +141,298 @@ struct Puzzles {
     puzzles: Vec<FilterTerm>,
 }
 
+#[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
+struct SolveArgs {
+    #[command(flatten)]
+    puzzles: Puzzles,
+
+    /// Stop as soon as downloading, parsing, or solving any puzzle failed.
+    ///
+    /// Solvers that are already running will not be interrupted,
+    /// but no new downloads or solvers will be started.
+    #[arg(long)]
+    fail_fast: bool,
+
+    /// Convert line endings and trailing newlines of puzzle inputs.
+    ///
+    /// Removes all carriage returns (e.g. from Windows line endings)
+    /// and makes sure each input ends with exactly one newline
+    /// before passing it to the solvers.
+    #[arg(long)]
+    normalize_input: bool,
+
+    /// Write all internal events to this file as JSON lines (for debugging).
+    ///
+    /// If the file exists already, it will be overwritten.
+    #[arg(long, hide = true, value_name = "FILE")]
+    dump_events: Option<PathBuf>,
+
+    /// Don't download any puzzle inputs.
+    ///
+    /// Puzzles whose inputs haven't been downloaded before
+    /// will be skipped instead.
+    #[arg(long)]
+    offline: bool,
+
+    /// Print how much of each puzzle's time was spent parsing its input.
+    ///
+    /// When all puzzles are done, prints a line for each puzzle showing
+    /// the percentage of parsing time relative to the total time
+    /// spent parsing and solving.
+    #[arg(long)]
+    profile_parse: bool,
+
+    /// The order of the lines printed by `--profile-parse`.
+    ///
+    /// `time` lists the slowest puzzles first, ordered by the total time
+    /// spent parsing and solving.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ORDER",
+        default_value_t = SummaryOrder::Id,
+        requires = "profile_parse"
+    )]
+    sort_summary: SummaryOrder,
+
+    /// Read and save personal puzzle inputs in this directory.
+    ///
+    /// By default, personal puzzle inputs are kept in the cache directory
+    /// (see `login`). The directory must exist already.
+    /// You can also set the `AOC_INPUT_DIR` environment variable instead.
+    #[arg(long, value_name = "DIR")]
+    input_dir: Option<PathBuf>,
+
+    /// Only solve puzzles that have this tag (e.g. `grid`).
+    ///
+    /// Can be passed multiple times to select puzzles
+    /// that have at least one of the given tags.
+    #[arg(long = "tag", value_name = "TAG")]
+    tags: Vec<String>,
+
+    /// Colors to use when displaying puzzle states.
+    #[arg(long, value_enum, default_value_t = Theme::Mono)]
+    theme: Theme,
+
+    /// Use an ASCII spinner instead of braille characters.
+    ///
+    /// Useful if your terminal font can't display braille characters.
+    #[arg(long)]
+    ascii_spinner: bool,
+
+    /// Truncate answers wider than this many characters.
+    ///
+    /// Keeps the table aligned if some answers are very long.
+    /// Truncated answers will be printed in full after all puzzles are done.
+    #[arg(long, value_name = "CHARS", value_parser = parse_max_answer_width)]
+    max_answer_width: Option<usize>,
+
+    /// Make the answer columns exactly this many characters wide.
+    ///
+    /// Keeps the table layout the same across runs, e.g. for diffing.
+    /// Truncated answers will be printed in full after all puzzles are done.
+    #[arg(
+        long = "fixed-width-answers",
+        value_name = "CHARS",
+        value_parser = parse_max_answer_width,
+        conflicts_with = "max_answer_width"
+    )]
+    fixed_answer_width: Option<usize>,
+
+    /// Solve one puzzle part at a time on a single thread.
+    ///
+    /// Slower than solving in parallel (the default),
+    /// but makes the times of each part easier to compare across runs.
+    #[arg(long)]
+    single_thread: bool,
+
+    /// Solve the selected puzzle again each time its input changes.
+    ///
+    /// Requires selecting a single puzzle, e.g. `aoc solve y21d01 --watch`.
+    /// Prints the answers as plain text instead of showing the table.
+    /// Press Ctrl-C to stop.
+    #[arg(long)]
+    watch: bool,
+}
+
+#[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
+struct StatsArgs {
+    #[command(flatten)]
+    puzzles: Puzzles,
+
+    /// Also print the arithmetic mean of times, ranks, and scores.
+    ///
+    /// Note that `>24h` times are excluded when computing the mean time.
+    #[arg(long)]
+    avg: bool,
+
+    /// Omit the `Score` columns and use less spacing between columns.
+    #[arg(long)]
+    compact: bool,
+
+    /// Print only the `MIN`/`MED`/`MAX` rows, omitting individual days.
+    #[arg(long)]
+    total_only: bool,
+
+    /// Order the days by rank or score, best first.
+    ///
+    /// Days in which the part selected by `--sort-part` was not solved
+    /// are listed last.
+    #[arg(long, value_enum, value_name = "KEY")]
+    sort: Option<SortKey>,
+
+    /// The part whose rank or score `--sort` uses, e.g. `p2`.
+    #[arg(long, value_name = "PART", default_value = "p1", requires = "sort")]
+    sort_part: Id<Part>,
+
+    /// Write the statistics to this file instead of to stdout.
+    ///
+    /// If the file exists already, it will be overwritten.
+    #[arg(long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
+struct SubmitArgs {
+    /// The puzzle part to submit the answer for, e.g. `y21d01p2`.
+    puzzle: Id<(Year, Day, Part)>,
+
+    /// The answer to submit.
+    answer: String,
+}
+
+#[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
+struct ResetArgs {
+    /// Confirm that the cached files should be deleted.
+    #[arg(long)]
+    yes: bool,
+}
+
+#[derive(clap::Args, Debug, Clone, PartialEq, Hash, Eq)]
+struct TestExamplesArgs {
+    #[command(flatten)]
+    puzzles: Puzzles,
+}
+
 impl From<Puzzles> for Filter {
     fn from(val: Puzzles) -> Self {
         val.puzzles.into()
     }
 }
 
-pub fn parse_args_from_env_or_exit() -> Command {
-    parse_or_exit(std::env::args_os())
+fn parse_max_answer_width(s: &str) -> Result<usize, String> {
+    // The spinner is two characters wide.
+    match s.parse::<usize>() {
+        Ok(width) if width >= 2 => Ok(width),
+        _ => Err(String::from("expected a number of at least 2")),
+    }
+}
+
+pub fn parse_args_from_env_or_exit() -> (GlobalOptions, Command) {
+    parse_with_options_or_exit(std::env::args_os())
+}
+
+/// Returns the version string printed by `--version`, i.e. the package version
+/// followed by the output of `git describe --always --dirty` (if available
+/// at build time).
+fn version() -> &'static str {
+    static VERSION: OnceLock<String> = OnceLock::new();
+    VERSION.get_or_init(|| {
+        let pkg_version = env!("CARGO_PKG_VERSION");
+        let git_describe = option_env!("AOC_GIT_DESCRIBE");
+        version_string(pkg_version, git_describe)
+    })
+}
+
+fn version_string(pkg_version: &str, git_describe: Option<&str>) -> String {
+    match git_describe {
+        Some(describe) => format!("{pkg_version} ({describe})"),
+        None => pkg_version.to_owned(),
+    }
 }
 
+#[cfg(test)]
 fn parse_or_exit<IntoIter, T>(args: IntoIter) -> Command
+where
+    IntoIter: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let (_, command) = parse_with_options_or_exit(args);
+    command
+}
+
+fn parse_with_options_or_exit<IntoIter, T>(
+    args: IntoIter,
+) -> (GlobalOptions, Command)
 where
     IntoIter: IntoIterator<Item = T>,
     T: Into<std::ffi::OsString> + Clone,
 {
     use clap::Parser;
     let args = CliArgs::parse_from(args);
-    match args.command {
-        None => Command::Solve(Filter::default()),
+    let options = GlobalOptions {
+        local_data: args.local_data,
+    };
+
+    let command = match args.command {
+        None => Command::Solve(
+            Filter::default(),
+            SolveOptions::default(),
+            UiConfig::default(),
+        ),
         Some(CliCommand::Login) => Command::Login,
         Some(CliCommand::Logout) => Command::Logout,
-        Some(CliCommand::Solve(puzzles)) => {
-            Command::Solve(Filter::from(puzzles))
+        Some(CliCommand::Solve(args)) => {
+            let options = SolveOptions {
+                fail_fast: args.fail_fast,
+                normalize_input: args.normalize_input,
+                dump_events: args.dump_events,
+                offline: args.offline,
+                profile_parse: args.profile_parse,
+                sort_summary: args.sort_summary,
+                input_dir: args.input_dir,
+                tags: args.tags,
+                single_thread: args.single_thread,
+                watch: args.watch,
+            };
+            let spinners = if args.ascii_spinner {
+                ASCII_SPINNERS
+            } else {
+                UiConfig::default().spinners
+            };
+            let ui_config = UiConfig {
+                spinners,
+                theme: args.theme,
+                max_answer_width: args.max_answer_width,
+                fixed_answer_width: args.fixed_answer_width,
+                ..UiConfig::default()
+            };
+            Command::Solve(Filter::from(args.puzzles), options, ui_config)
         }
-        Some(CliCommand::Stats(puzzles)) => {
-            Command::Stats(Filter::from(puzzles))
+        Some(CliCommand::Stats(args)) => {
+            let layout = Layout {
+                avg: args.avg,
+                compact: args.compact,
+                total_only: args.total_only,
+                sort: args
+                    .sort
+                    .map(|key| (args.sort_part.0, key)),
+            };
+            Command::Stats(Filter::from(args.puzzles), layout, args.output)
         }
-    }
+        Some(CliCommand::Submit(args)) => {
+            Command::Submit(args.puzzle, args.answer)
+        }
+        Some(CliCommand::TestExamples(args)) => {
+            Command::TestExamples(Filter::from(args.puzzles))
+        }
+        Some(CliCommand::Doctor) => Command::Doctor,
+        Some(CliCommand::CacheStats) => Command::CacheStats,
+        Some(CliCommand::Reset(args)) => Command::Reset(args.yes),
+        Some(CliCommand::Where) => Command::Where,
+    };
+
+    (options, command)
 }
 
 #[cfg(test)]
@@ -111,6 +440,7 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
+    use crate::ident::part::{P1, P2};
 
     // First parameter references name of the program/binary.
     // It does not need to be checked. Thus, we use an empty string here.
@@ -131,6 +461,39 @@ mod tests {
         };
     }
 
+    #[test]
+    fn parse_cache_stats() {
+        match super::parse_or_exit(["", "cache-stats"]) {
+            Command::CacheStats => (),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+    }
+
+    #[test_case(&["", "reset"], false)]
+    #[test_case(&["", "reset", "--yes"], true)]
+    fn parse_reset(args: &[&str], expected: bool) {
+        match super::parse_or_exit(args) {
+            Command::Reset(yes) => assert_eq!(yes, expected),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+    }
+
+    #[test]
+    fn parse_where() {
+        match super::parse_or_exit(["", "where"]) {
+            Command::Where => (),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+    }
+
+    #[test]
+    fn parse_doctor() {
+        match super::parse_or_exit(["", "doctor"]) {
+            Command::Doctor => (),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+    }
+
     #[test_case(
         &[""],
         vec![];
@@ -172,13 +535,240 @@ mod tests {
     fn parse_solve(args: &[&str], expected: Vec<FilterTerm>) {
         let expected = Filter::from(expected);
         let actual = match super::parse_or_exit(args) {
-            Command::Solve(actual) => actual,
+            Command::Solve(actual, options, ui_config) => {
+                assert_eq!(options, SolveOptions::default());
+                assert_eq!(ui_config, UiConfig::default());
+                actual
+            }
             others => panic!("Unexpected result: {others:?}"),
         };
 
         assert_eq!(actual, expected);
     }
 
+    #[test_case(&["", "solve", "--fail-fast"], vec![]; "Without filters")]
+    #[test_case(
+        &["", "solve", "--fail-fast", "y21"],
+        vec!["y21".parse().unwrap()];
+        "With filters")]
+    fn parse_solve_fail_fast(args: &[&str], expected: Vec<FilterTerm>) {
+        let expected = Filter::from(expected);
+        let (actual, options) = match super::parse_or_exit(args) {
+            Command::Solve(actual, options, _) => (actual, options),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(actual, expected);
+        assert!(options.fail_fast);
+        assert!(!options.normalize_input);
+    }
+
+    #[test]
+    fn parse_solve_normalize_input() {
+        let options =
+            match super::parse_or_exit(["", "solve", "--normalize-input"]) {
+                Command::Solve(_, options, _) => options,
+                others => panic!("Unexpected result: {others:?}"),
+            };
+
+        assert!(options.normalize_input);
+        assert!(!options.fail_fast);
+    }
+
+    #[test]
+    fn parse_solve_dump_events() {
+        let options = match super::parse_or_exit([
+            "",
+            "solve",
+            "--dump-events",
+            "events.jsonl",
+        ]) {
+            Command::Solve(_, options, _) => options,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(options.dump_events, Some(PathBuf::from("events.jsonl")));
+    }
+
+    #[test]
+    fn parse_solve_offline() {
+        let options = match super::parse_or_exit(["", "solve", "--offline"]) {
+            Command::Solve(_, options, _) => options,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert!(options.offline);
+        assert!(!options.fail_fast);
+    }
+
+    #[test]
+    fn parse_solve_profile_parse() {
+        let options =
+            match super::parse_or_exit(["", "solve", "--profile-parse"]) {
+                Command::Solve(_, options, _) => options,
+                others => panic!("Unexpected result: {others:?}"),
+            };
+
+        assert!(options.profile_parse);
+        assert!(!options.offline);
+    }
+
+    #[test_case(&["", "solve", "--profile-parse"], SummaryOrder::Id)]
+    #[test_case(
+        &["", "solve", "--profile-parse", "--sort-summary", "time"],
+        SummaryOrder::Time)]
+    fn parse_solve_sort_summary(args: &[&str], expected: SummaryOrder) {
+        let options = match super::parse_or_exit(args) {
+            Command::Solve(_, options, _) => options,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(options.sort_summary, expected);
+    }
+
+    #[test]
+    fn parse_solve_sort_summary_requires_profile_parse() {
+        use clap::Parser;
+        let result =
+            CliArgs::try_parse_from(["", "solve", "--sort-summary", "time"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_solve_input_dir() {
+        let options = match super::parse_or_exit([
+            "",
+            "solve",
+            "--input-dir",
+            "my/inputs",
+        ]) {
+            Command::Solve(_, options, _) => options,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(options.input_dir, Some(PathBuf::from("my/inputs")));
+    }
+
+    #[test]
+    fn parse_solve_single_thread() {
+        let options =
+            match super::parse_or_exit(["", "solve", "--single-thread"]) {
+                Command::Solve(_, options, _) => options,
+                others => panic!("Unexpected result: {others:?}"),
+            };
+
+        assert!(options.single_thread);
+        assert!(!options.fail_fast);
+    }
+
+    #[test]
+    fn parse_solve_watch() {
+        let options =
+            match super::parse_or_exit(["", "solve", "y21d01", "--watch"]) {
+                Command::Solve(_, options, _) => options,
+                others => panic!("Unexpected result: {others:?}"),
+            };
+
+        assert!(options.watch);
+    }
+
+    #[test_case(&["", "solve"], Theme::Mono)]
+    #[test_case(&["", "solve", "--theme", "dark"], Theme::Dark)]
+    #[test_case(&["", "solve", "--theme", "light"], Theme::Light)]
+    #[test_case(&["", "solve", "--theme", "mono"], Theme::Mono)]
+    fn parse_solve_theme(args: &[&str], expected: Theme) {
+        let ui_config = match super::parse_or_exit(args) {
+            Command::Solve(_, _, ui_config) => ui_config,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(ui_config.theme, expected);
+    }
+
+    #[test_case(&["", "solve"], UiConfig::default().spinners)]
+    #[test_case(&["", "solve", "--ascii-spinner"], ASCII_SPINNERS)]
+    fn parse_solve_ascii_spinner(args: &[&str], expected: &[&str]) {
+        let ui_config = match super::parse_or_exit(args) {
+            Command::Solve(_, _, ui_config) => ui_config,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(ui_config.spinners, expected);
+    }
+
+    #[test_case(&["", "solve"], None)]
+    #[test_case(&["", "solve", "--max-answer-width", "8"], Some(8))]
+    fn parse_solve_max_answer_width(args: &[&str], expected: Option<usize>) {
+        let ui_config = match super::parse_or_exit(args) {
+            Command::Solve(_, _, ui_config) => ui_config,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(ui_config.max_answer_width, expected);
+    }
+
+    #[test_case(&[""], false)]
+    #[test_case(&["", "--local-data"], true)]
+    #[test_case(&["", "stats", "--local-data"], true)]
+    #[test_case(&["", "--local-data", "doctor"], true)]
+    fn parse_local_data(args: &[&str], expected: bool) {
+        let (options, _) = super::parse_with_options_or_exit(args);
+        assert_eq!(options.local_data, expected);
+    }
+
+    #[test_case(&["", "solve"], None)]
+    #[test_case(&["", "solve", "--fixed-width-answers", "12"], Some(12))]
+    fn parse_solve_fixed_width_answers(args: &[&str], expected: Option<usize>) {
+        let ui_config = match super::parse_or_exit(args) {
+            Command::Solve(_, _, ui_config) => ui_config,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(ui_config.fixed_answer_width, expected);
+    }
+
+    #[test]
+    fn parse_solve_fixed_width_answers_conflicts_with_max() {
+        use clap::Parser;
+        let result = CliArgs::try_parse_from([
+            "",
+            "solve",
+            "--fixed-width-answers",
+            "12",
+            "--max-answer-width",
+            "8",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test_case("0")]
+    #[test_case("1")]
+    #[test_case("-1"; "negative")]
+    #[test_case("foo")]
+    fn parse_max_answer_width_fails(arg: &str) {
+        assert!(super::parse_max_answer_width(arg).is_err());
+    }
+
+    #[test]
+    fn parse_solve_tags() {
+        let (filter, options) = match super::parse_or_exit([
+            "",
+            "solve",
+            "y24",
+            "--tag",
+            "grid",
+            "--tag",
+            "simulation",
+        ]) {
+            Command::Solve(filter, options, _) => (filter, options),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        let expected: Vec<FilterTerm> = vec!["y24".parse().unwrap()];
+        assert_eq!(filter, Filter::from(expected));
+        assert_eq!(options.tags, vec!["grid", "simulation"]);
+    }
+
     #[test_case(
         &["", "stats"],
         vec![];
@@ -200,10 +790,160 @@ mod tests {
     fn parse_stats(args: &[&str], expected: Vec<FilterTerm>) {
         let expected = Filter::from(expected);
         let actual = match super::parse_or_exit(args) {
-            Command::Stats(actual) => actual,
+            Command::Stats(actual, layout, output) => {
+                assert_eq!(layout, Layout::default());
+                assert_eq!(output, None);
+                actual
+            }
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test_case(&["", "stats", "--avg"], vec![]; "Without filters")]
+    #[test_case(
+        &["", "stats", "--avg", "y21"],
+        vec!["y21".parse().unwrap()];
+        "With filters")]
+    fn parse_stats_avg(args: &[&str], expected: Vec<FilterTerm>) {
+        let expected = Filter::from(expected);
+        let (actual, layout) = match super::parse_or_exit(args) {
+            Command::Stats(actual, layout, _) => (actual, layout),
             others => panic!("Unexpected result: {others:?}"),
         };
 
         assert_eq!(actual, expected);
+        assert!(layout.avg);
+    }
+
+    #[test]
+    fn parse_stats_total_only() {
+        let args = ["", "stats", "--total-only"];
+        let layout = match super::parse_or_exit(args) {
+            Command::Stats(_, layout, _) => layout,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert!(layout.total_only);
+        assert!(!layout.compact);
+    }
+
+    #[test_case(&["", "stats"], None)]
+    #[test_case(&["", "stats", "--sort", "rank"], Some((P1, SortKey::Rank)))]
+    #[test_case(
+        &["", "stats", "--sort", "score", "--sort-part", "p2"],
+        Some((P2, SortKey::Score)))]
+    fn parse_stats_sort(args: &[&str], expected: Option<(Part, SortKey)>) {
+        let layout = match super::parse_or_exit(args) {
+            Command::Stats(_, layout, _) => layout,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(layout.sort, expected);
+    }
+
+    #[test]
+    fn parse_stats_sort_part_requires_sort() {
+        use clap::Parser;
+        let result =
+            CliArgs::try_parse_from(["", "stats", "--sort-part", "p2"]);
+        assert!(result.is_err());
+    }
+
+    #[test_case(&["", "stats", "--compact"], false)]
+    #[test_case(&["", "stats", "--compact", "--avg"], true)]
+    fn parse_stats_compact(args: &[&str], avg: bool) {
+        let layout = match super::parse_or_exit(args) {
+            Command::Stats(_, layout, _) => layout,
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert!(layout.compact);
+        assert_eq!(layout.avg, avg);
+    }
+
+    #[test_case(
+        &["", "stats", "--output", "stats.txt"],
+        vec![];
+        "Without filters")]
+    #[test_case(
+        &["", "stats", "y21", "--output", "stats.txt"],
+        vec!["y21".parse().unwrap()];
+        "With filters")]
+    fn parse_stats_output(args: &[&str], expected: Vec<FilterTerm>) {
+        let expected = Filter::from(expected);
+        let (actual, output) = match super::parse_or_exit(args) {
+            Command::Stats(actual, _, output) => (actual, output),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+
+        assert_eq!(actual, expected);
+        assert_eq!(output, Some(PathBuf::from("stats.txt")));
+    }
+
+    #[test]
+    fn parse_submit() -> lazy_errors::Result<()> {
+        match super::parse_or_exit(["", "submit", "y24d01p2", "1337"]) {
+            Command::Submit(id, answer) => {
+                assert_eq!(id, "y24d01p2".parse()?);
+                assert_eq!(answer, "1337");
+            }
+            others => panic!("Unexpected result: {others:?}"),
+        };
+        Ok(())
+    }
+
+    #[test_case(&["", "test-examples"], vec![]; "Without filters")]
+    #[test_case(
+        &["", "test-examples", "y24d01"],
+        vec!["y24d01".parse().unwrap()];
+        "With filters")]
+    fn parse_test_examples(args: &[&str], expected: Vec<FilterTerm>) {
+        let expected = Filter::from(expected);
+        match super::parse_or_exit(args) {
+            Command::TestExamples(actual) => assert_eq!(actual, expected),
+            others => panic!("Unexpected result: {others:?}"),
+        };
+    }
+
+    #[test_case("0.1.0", None, "0.1.0")]
+    #[test_case("0.1.0", Some("v0.1.0"), "0.1.0 (v0.1.0)")]
+    #[test_case(
+        "0.1.0",
+        Some("v0.1.0-3-g1234abc-dirty"),
+        "0.1.0 (v0.1.0-3-g1234abc-dirty)"
+    )]
+    fn version_string(pkg: &str, describe: Option<&str>, expected: &str) {
+        assert_eq!(super::version_string(pkg, describe), expected);
+    }
+
+    #[test_case("1234abc", true; "commit")]
+    #[test_case("1234abc-dirty", true; "dirty commit")]
+    #[test_case("v0.1.0", true; "tag")]
+    #[test_case("v0.1.0-3-g1234abc-dirty", true; "commits after tag")]
+    #[test_case("", false; "empty")]
+    #[test_case("fatal: No names found", false; "error message")]
+    fn git_describe_shape(describe: &str, expected: bool) {
+        assert_eq!(looks_like_git_describe(describe), expected);
+    }
+
+    /// Checks what the build script actually exported, if anything
+    /// (it won't have exported anything when building from a tarball).
+    #[test]
+    fn version_contains_git_describe() {
+        let Some(describe) = option_env!("AOC_GIT_DESCRIBE") else {
+            return;
+        };
+
+        assert!(looks_like_git_describe(describe), "{describe}");
+        assert!(super::version().ends_with(&format!("({describe})")));
+    }
+
+    fn looks_like_git_describe(describe: &str) -> bool {
+        lazy_regex::regex_is_match!(
+            r"^(v\d+\.\d+\.\d+(-\d+-g[0-9a-f]{4,})?|[0-9a-f]{4,})(-dirty)?$",
+            describe
+        )
     }
 }
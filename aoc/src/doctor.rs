@@ -0,0 +1,249 @@
+use std::{fmt, path::Path};
+
+use lazy_errors::Result;
+
+use crate::fs::{is_writable_dir, Config};
+
+#[derive(Debug, Clone, Copy, PartialEq, Hash, Eq)]
+pub enum Status {
+    Ok,
+    /// Something that may be intentional but limits what `aoc` can do.
+    Warning,
+    /// Something that prevents `aoc` from working at all.
+    Broken,
+}
+
+/// The result of checking a single aspect of the user's setup.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+pub struct Check {
+    /// Identifies the check, e.g. `"session cookie"`. Not printed.
+    pub name:    &'static str,
+    pub status:  Status,
+    pub message: String,
+}
+
+impl Check {
+    fn new(
+        name: &'static str,
+        status: Status,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            name,
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let marker = match self.status {
+            Status::Ok => "[ OK ]",
+            Status::Warning => "[WARN]",
+            Status::Broken => "[FAIL]",
+        };
+
+        write!(f, "{marker} {}", self.message)
+    }
+}
+
+/// Checks whether the directories and the session cookie are set up.
+///
+/// Problems are reported as failed checks instead of errors,
+/// so that all checks will be run.
+pub fn check(config: &Config) -> Vec<Check> {
+    vec![
+        check_repo_dir(config.repo_dir()),
+        check_writable_dir("Config", config.config_dir(), Status::Broken),
+        check_writable_dir("Cache", config.cache_dir(), Status::Broken),
+        // May be set via `AOC_INPUT_DIR`, which is not checked at startup.
+        check_writable_dir(
+            "Personal puzzle inputs",
+            &config.personal_puzzle_inputs_dir(),
+            Status::Broken,
+        ),
+        // The data directory is only needed by `aoc stats`.
+        check_writable_dir("Data", config.data_dir(), Status::Warning),
+        check_session_cookie(config),
+        check_count(
+            "inputs",
+            config.count_personal_puzzle_inputs(),
+            "personal puzzle input(s) cached",
+        ),
+        check_count(
+            "leaderboards",
+            config.count_personal_leaderboards(),
+            "personal leaderboard(s) found",
+        ),
+    ]
+}
+
+fn check_session_cookie(config: &Config) -> Check {
+    let name = "session cookie";
+    let cookie = config.session_cookie_path();
+    let cookie = cookie.display();
+    match config.read_session_cookie() {
+        Ok(Some(_)) => {
+            Check::new(name, Status::Ok, format!("Session cookie: {cookie}"))
+        }
+        Ok(None) => Check::new(
+            name,
+            Status::Warning,
+            "Not logged in (run `aoc login` to download inputs automatically)",
+        ),
+        Err(e) => Check::new(
+            name,
+            Status::Broken,
+            format!("Session cookie: {cookie} ({e})"),
+        ),
+    }
+}
+
+fn check_count(name: &'static str, count: Result<usize>, what: &str) -> Check {
+    match count {
+        Ok(count) => Check::new(name, Status::Ok, format!("{count} {what}")),
+        Err(e) => Check::new(name, Status::Broken, format!("{what}: {e}")),
+    }
+}
+
+fn check_repo_dir(path: &Path) -> Check {
+    let (status, problem) = if path.is_dir() {
+        (Status::Ok, "")
+    } else {
+        (Status::Broken, " (does not exist)")
+    };
+
+    let path = path.display();
+    let message = format!("Repository directory: {path}{problem}");
+    Check::new("repo dir", status, message)
+}
+
+fn check_writable_dir(
+    name: &'static str,
+    path: &Path,
+    severity: Status,
+) -> Check {
+    let (status, problem) = if !path.is_dir() {
+        (severity, " (does not exist)")
+    } else if !is_writable_dir(path) {
+        (severity, " (not writable)")
+    } else {
+        (Status::Ok, "")
+    };
+
+    let path = path.display();
+    Check::new(name, status, format!("{name} directory: {path}{problem}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fs::{create_config_for, tempdir},
+        ident::{day::*, year::*},
+    };
+
+    use super::*;
+
+    fn find<'a>(checks: &'a [Check], name: &str) -> &'a Check {
+        checks
+            .iter()
+            .find(|c| c.name == name)
+            .unwrap_or_else(|| panic!("No check named '{name}'"))
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn check_tempdir() -> Result<()> {
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+
+        let checks = check(&config);
+        let statuses: Vec<Status> = checks
+            .iter()
+            .map(|c| c.status)
+            .collect();
+        assert_eq!(statuses, vec![
+            Status::Ok,
+            Status::Ok,
+            Status::Ok,
+            Status::Ok,
+            Status::Ok,
+            Status::Warning,
+            Status::Ok,
+            Status::Ok,
+        ]);
+        assert!(find(&checks, "session cookie")
+            .to_string()
+            .starts_with("[WARN] Not logged in"));
+        assert_eq!(
+            find(&checks, "inputs").message,
+            "0 personal puzzle input(s) cached"
+        );
+
+        config.save_session_cookie("mock cookie")?;
+        config.save_personal_puzzle_input(Y21, D01, "mock input")?;
+        config.save_personal_puzzle_input(Y21, D02, "mock input")?;
+
+        let checks = check(&config);
+        let cookie = config.session_cookie_path();
+        let cookie = cookie.display();
+        assert_eq!(
+            find(&checks, "session cookie").to_string(),
+            format!("[ OK ] Session cookie: {cookie}")
+        );
+        assert_eq!(
+            find(&checks, "inputs").message,
+            "2 personal puzzle input(s) cached"
+        );
+        assert_eq!(
+            find(&checks, "leaderboards").message,
+            "0 personal leaderboard(s) found"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn check_unreadable_session_cookie() -> Result<()> {
+        let tempdir = tempdir()?;
+        let config = create_config_for(&tempdir)?;
+
+        // Reading a directory as if it were a file fails.
+        std::fs::create_dir(config.session_cookie_path()).unwrap();
+
+        let checks = check(&config);
+        let cookie = find(&checks, "session cookie");
+        assert_eq!(cookie.status, Status::Broken);
+        assert!(cookie
+            .message
+            .contains("Failed to read session cookie"));
+
+        // All other checks still ran.
+        assert_eq!(
+            find(&checks, "inputs").message,
+            "0 personal puzzle input(s) cached"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `tempdir`
+    fn check_missing_dir() -> Result<()> {
+        let tempdir = tempdir()?;
+        let path = tempdir.path().join("missing");
+
+        let check = check_writable_dir("Cache", &path, Status::Broken);
+        assert_eq!(check.status, Status::Broken);
+        assert!(check
+            .message
+            .ends_with("(does not exist)"));
+
+        let check = check_writable_dir("Cache", tempdir.path(), Status::Broken);
+        assert_eq!(check.status, Status::Ok);
+
+        Ok(())
+    }
+}
@@ -1,25 +1,47 @@
-use std::time::{Duration, Instant};
+use std::{
+    pin::pin,
+    time::{Duration, Instant, SystemTime},
+};
 
+use futures::{Stream, StreamExt};
 use lazy_errors::{prelude::*, Result};
-use tokio::{sync::mpsc, task};
+use tokio::{
+    sync::{mpsc, watch},
+    task,
+};
 
 use crate::{
+    fetcher::{self, FetchError},
     fs::Config,
-    ident::{Day, Year},
+    ident::{Day, Id, Year},
     runner::Input,
-    solver::{Event, Parts, Solver, State, Step},
+    solver::{Event, Parts, SolveOptions, Solver, State, Step},
 };
 
+/// Why a puzzle is skipped if its input is not cached in offline mode.
+const REASON_OFFLINE: &str = "offline";
+
+/// Why a puzzle is skipped if adventofcode.com recently told us
+/// that the puzzle has not been released yet.
+const REASON_NOT_RELEASED: &str = "puzzle has not been released yet";
+
+/// How long to wait at least between two download progress events.
+/// The UI does not redraw more often anyway.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct Downloader;
 
 impl Downloader {
     pub fn spawn(
         config: Config,
         puzzles: Vec<(Solver, Parts)>,
+        options: &SolveOptions,
+        shutdown: watch::Receiver<bool>,
         tx_next: mpsc::Sender<(Solver, Parts, Input)>,
         tx_ui: mpsc::Sender<Event>,
     ) -> Self {
-        task::spawn(run(config, puzzles, tx_next, tx_ui));
+        let offline = options.offline;
+        task::spawn(run(config, puzzles, offline, shutdown, tx_next, tx_ui));
         Self {}
     }
 }
@@ -27,6 +49,8 @@ impl Downloader {
 async fn run(
     mut config: Config,
     puzzles: Vec<(Solver, Parts)>,
+    offline: bool,
+    mut shutdown: watch::Receiver<bool>,
     tx_next: mpsc::Sender<(Solver, Parts, Input)>,
     tx_ui: mpsc::Sender<Event>,
 ) {
@@ -36,32 +60,81 @@ async fn run(
     let mut queue = vec![];
 
     for (solver, parts) in puzzles {
+        if is_stopped(&tx_ui, &shutdown) {
+            return; // UI stopped early, e.g. because of `--fail-fast`
+        }
+
         let input: Result<Option<String>> =
             config.read_personal_puzzle_input(solver.year(), solver.day());
 
-        enqueue_or_forward(solver, parts, input, &mut queue, &tx_next, &tx_ui)
-            .await
-            .expect("Failed to enqueue or forward solver");
+        let result = enqueue_or_forward(
+            solver, parts, input, offline, &mut queue, &tx_next, &tx_ui,
+        )
+        .await;
+
+        if !is_stopped(&tx_ui, &shutdown) {
+            result.expect("Failed to enqueue or forward solver");
+        }
     }
 
     for (solver, parts) in queue {
+        if is_stopped(&tx_ui, &shutdown) {
+            return; // UI stopped early, e.g. because of `--fail-fast`
+        }
+
+        let result =
+            skip_if_not_yet_released(&solver, parts, &config, &tx_ui).await;
+        match result {
+            Ok(true) => continue,
+            Ok(false) => (),
+            Err(e) => {
+                let (year, day) = (solver.year(), solver.day());
+                let event = failed(year, day, Duration::ZERO, e);
+                if send(event, &tx_ui).await.is_err() {
+                    return; // UI stopped early
+                }
+                continue;
+            }
+        }
+
         // Serialize requests to keep load on adventofcode.com low.
-        download_and_cache_and_forward(
+        let download = download_and_cache_and_forward(
             solver,
             parts,
             &mut config,
             &tx_next,
             &tx_ui,
-        )
-        .await
-        .expect("Failed to download puzzle input");
+        );
+
+        // Cancel the download if the user quits in the meantime.
+        let result = tokio::select! {
+            result = download => result,
+            Ok(_) = shutdown.wait_for(|&stop| stop) => return,
+        };
+
+        if !is_stopped(&tx_ui, &shutdown) {
+            result.expect("Failed to download puzzle input");
+        }
     }
 }
 
+/// Returns whether the UI stopped early, e.g. because of `--fail-fast`,
+/// or because the user quit.
+fn is_stopped(
+    tx_ui: &mpsc::Sender<Event>,
+    shutdown: &watch::Receiver<bool>,
+) -> bool {
+    tx_ui.is_closed() || *shutdown.borrow()
+}
+
+/// Forwards `solver` to the next stage if its input is cached already.
+/// Otherwise, enqueues it for downloading, unless we're `offline`,
+/// in which case the puzzle is skipped (see [`skip_unavailable`]).
 async fn enqueue_or_forward(
     solver: Solver,
     parts: Parts,
     input_maybe: Result<Option<String>>,
+    offline: bool,
     queue: &mut Vec<(Solver, Parts)>,
     tx_next: &mpsc::Sender<(Solver, Parts, Input)>,
     tx_ui: &mpsc::Sender<Event>,
@@ -70,6 +143,9 @@ async fn enqueue_or_forward(
     let day = solver.day();
 
     match input_maybe {
+        Ok(None) if offline => {
+            skip_unavailable(year, day, parts, REASON_OFFLINE, tx_ui).await?;
+        }
         Ok(None) => queue.push((solver, parts)),
         Ok(Some(input)) => {
             send(skipped(year, day), tx_ui).await?;
@@ -83,6 +159,55 @@ async fn enqueue_or_forward(
     Ok(())
 }
 
+/// If adventofcode.com recently told us that the puzzle has not been
+/// released yet, skips the puzzle (see [`skip_unavailable`])
+/// instead of sending a request that is doomed to fail again.
+///
+/// Returns whether the puzzle was skipped.
+async fn skip_if_not_yet_released(
+    solver: &Solver,
+    parts: Parts,
+    config: &Config,
+    tx_ui: &mpsc::Sender<Event>,
+) -> Result<bool> {
+    let year = solver.year();
+    let day = solver.day();
+
+    if !config.is_marked_not_yet_released(year, day, SystemTime::now())? {
+        return Ok(false);
+    }
+
+    skip_unavailable(year, day, parts, REASON_NOT_RELEASED, tx_ui).await?;
+    Ok(true)
+}
+
+/// Marks the download of the puzzle as unavailable because of `reason`,
+/// and the steps that would have used the input as skipped.
+/// Parts that were not selected in `parts` are skipped anyways,
+/// so no events will be sent for them.
+async fn skip_unavailable(
+    year: Year,
+    day: Day,
+    parts: Parts,
+    reason: &'static str,
+    tx_ui: &mpsc::Sender<Event>,
+) -> Result<()> {
+    send(unavailable(year, day, reason), tx_ui).await?;
+    send(skipped_step(year, day, Step::Preproc), tx_ui).await?;
+
+    let steps: &[Step] = match parts {
+        Parts::First => &[Step::Part1],
+        Parts::Second => &[Step::Part2],
+        Parts::Both => &[Step::Part1, Step::Part2],
+    };
+
+    for &step in steps {
+        send(skipped_step(year, day, step), tx_ui).await?;
+    }
+
+    Ok(())
+}
+
 async fn download_and_cache_and_forward(
     solver: Solver,
     parts: Parts,
@@ -96,7 +221,7 @@ async fn download_and_cache_and_forward(
     let start_time = Instant::now();
     send(started(year, day, start_time), tx_ui).await?;
 
-    let result = download_and_cache(year, day, config).await;
+    let result = download_and_cache(year, day, start_time, config, tx_ui).await;
     let duration = start_time.elapsed();
 
     match result {
@@ -115,46 +240,103 @@ async fn download_and_cache_and_forward(
 async fn download_and_cache(
     year: Year,
     day: Day,
+    start_time: Instant,
     config: &mut Config,
+    tx_ui: &mpsc::Sender<Event>,
 ) -> Result<String> {
-    let session_cookie = match config.read_session_cookie() {
-        Ok(Some(cookie)) => cookie,
-        Ok(None) => return Err(err!("Not logged in")),
-        Err(e) => return Err(e),
-    };
+    let session_cookie = config.require_session_cookie()?;
 
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-    let Ok(response) = reqwest::Client::new()
-        .request(reqwest::Method::GET, url)
-        .header("Cookie", format!("session={session_cookie}"))
-        .send()
-        .await
-        .and_then(|r| r.error_for_status())
-    else {
-        // adventofcode.com sends HTTP 400 instead of HTTP 401,
-        // so we can't distinguish “real” errors.
-        return Err(err!("HTTP request failed. Are you logged in?"));
+    let url = Id((year, day)).input_url();
+    let response = match fetcher::get(&url, &session_cookie).await {
+        Ok(response) => response,
+        Err(FetchError::NotFound) => {
+            config.save_not_yet_released_marker(
+                year,
+                day,
+                SystemTime::now(),
+            )?;
+            return Err(err!("Puzzle has not been released yet"));
+        }
+        Err(e) => return Err(Error::wrap(e)),
     };
 
-    let input = response
-        .text()
-        .await
-        .or_wrap_with(|| "Failed to convert input to text")?;
+    let chunks = futures::stream::unfold(response, |mut response| async {
+        let chunk = response.chunk().await.transpose()?;
+        Some((chunk, response))
+    });
+
+    let input = receive_with_progress(
+        year,
+        day,
+        start_time,
+        PROGRESS_INTERVAL,
+        chunks,
+        tx_ui,
+    )
+    .await?;
 
     config.save_personal_puzzle_input(year, day, &input)?;
 
     Ok(input)
 }
 
+/// Collects all `chunks` into a string,
+/// sending a progress event to the UI after the first chunk
+/// and then at most once per `min_interval`.
+async fn receive_with_progress<B, E>(
+    year: Year,
+    day: Day,
+    start_time: Instant,
+    min_interval: Duration,
+    chunks: impl Stream<Item = Result<B, E>>,
+    tx_ui: &mpsc::Sender<Event>,
+) -> Result<String>
+where
+    B: AsRef<[u8]>,
+    E: Into<Stashable>,
+{
+    let mut chunks = pin!(chunks);
+    let mut body = Vec::new();
+    let mut last_sent: Option<Instant> = None;
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.or_wrap_with(|| "Failed to receive input")?;
+        body.extend_from_slice(chunk.as_ref());
+
+        let now = Instant::now();
+        if last_sent.is_some_and(|t| now - t < min_interval) {
+            continue;
+        }
+
+        send(progress(year, day, start_time, body.len()), tx_ui).await?;
+        last_sent = Some(now);
+    }
+
+    String::from_utf8(body).or_wrap_with(|| "Failed to convert input to text")
+}
+
 fn skipped(year: Year, day: Day) -> Event {
+    skipped_step(year, day, Step::Download)
+}
+
+fn skipped_step(year: Year, day: Day, step: Step) -> Event {
     Event {
         year,
         day,
-        step: Step::Download,
+        step,
         state: State::Skipped,
     }
 }
 
+fn unavailable(year: Year, day: Day, reason: &'static str) -> Event {
+    Event {
+        year,
+        day,
+        step: Step::Download,
+        state: State::Unavailable(reason),
+    }
+}
+
 fn started(year: Year, day: Day, t: Instant) -> Event {
     Event {
         year,
@@ -164,6 +346,15 @@ fn started(year: Year, day: Day, t: Instant) -> Event {
     }
 }
 
+fn progress(year: Year, day: Day, t: Instant, bytes: usize) -> Event {
+    Event {
+        year,
+        day,
+        step: Step::Download,
+        state: State::Progress(t, bytes),
+    }
+}
+
 fn succeeded(year: Year, day: Day, t: Duration) -> Event {
     Event {
         year,
@@ -190,3 +381,225 @@ where
         .await
         .or_wrap_with(|| "Failed to send data")
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::{
+        ident::{day::*, year::Y21},
+        solver,
+    };
+
+    use super::*;
+
+    fn mock_solve(_: &str) -> Result<usize> {
+        Ok(42)
+    }
+
+    #[tokio::test]
+    async fn offline_forwards_cached_puzzles_only() -> Result<()> {
+        let cached = solver!(Y21, D01, mock_solve, mock_solve);
+        let uncached = solver!(Y21, D02, mock_solve, mock_solve);
+
+        let (tx_next, mut rx_next) = mpsc::channel(10);
+        let (tx_ui, mut rx_ui) = mpsc::channel(10);
+        let mut queue = vec![];
+
+        let uncached_p2 = solver!(Y21, D03, mock_solve, mock_solve);
+
+        let puzzles = [
+            (cached, Parts::Both, Ok(Some(String::from("cached input")))),
+            (uncached, Parts::Both, Ok(None)),
+            (uncached_p2, Parts::Second, Ok(None)),
+        ];
+
+        for (solver, parts, input) in puzzles {
+            enqueue_or_forward(
+                solver, parts, input, true, &mut queue, &tx_next, &tx_ui,
+            )
+            .await?;
+        }
+        drop(tx_next);
+        drop(tx_ui);
+
+        assert!(queue.is_empty());
+
+        let (solver, _, input) = rx_next.recv().await.unwrap();
+        assert_eq!(solver.day(), D01);
+        assert_eq!(input, "cached input");
+        assert!(rx_next.recv().await.is_none());
+
+        let mut events = vec![];
+        while let Some(event) = rx_ui.recv().await {
+            let state = match event.state {
+                State::Skipped => "skipped",
+                State::Unavailable(reason) => reason,
+                others => panic!("Unexpected state: {others:?}"),
+            };
+            events.push((event.day, event.step, state));
+        }
+
+        assert_eq!(events, vec![
+            (D01, Step::Download, "skipped"),
+            (D02, Step::Download, "offline"),
+            (D02, Step::Preproc, "skipped"),
+            (D02, Step::Part1, "skipped"),
+            (D02, Step::Part2, "skipped"),
+            (D03, Step::Download, "offline"),
+            (D03, Step::Preproc, "skipped"),
+            (D03, Step::Part2, "skipped"),
+        ]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn no_events_after_shutdown() -> Result<()> {
+        let tempdir = crate::fs::tempdir()?;
+        let mut config = crate::fs::create_config_for(&tempdir)?;
+        for d in [D01, D02, D03] {
+            config.save_personal_puzzle_input(Y21, d, "cached input")?;
+        }
+
+        let puzzles = vec![
+            (solver!(Y21, D01, mock_solve, mock_solve), Parts::Both),
+            (solver!(Y21, D02, mock_solve, mock_solve), Parts::Both),
+            (solver!(Y21, D03, mock_solve, mock_solve), Parts::Both),
+        ];
+
+        let (tx_shutdown, rx_shutdown) = watch::channel(false);
+        let (tx_next, mut rx_next) = mpsc::channel(10);
+        let (tx_ui, mut rx_ui) = mpsc::channel(1);
+
+        // Block the downloader when it tries to send its first event.
+        send(skipped(Y21, D16), &tx_ui).await?;
+        task::spawn(run(config, puzzles, false, rx_shutdown, tx_next, tx_ui));
+
+        // Simulate the user quitting while the downloader is blocked.
+        task::yield_now().await;
+        tx_shutdown.send_replace(true);
+
+        let mut days = vec![];
+        while let Some(event) = rx_ui.recv().await {
+            days.push(event.day);
+        }
+
+        // The first event may have been in-flight already.
+        assert!(days == [D16] || days == [D16, D01]);
+
+        while let Some((solver, _, _)) = rx_next.recv().await {
+            assert_eq!(solver.day(), D01);
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn skips_puzzles_marked_as_not_yet_released() -> Result<()> {
+        let tempdir = crate::fs::tempdir()?;
+        let mut config = crate::fs::create_config_for(&tempdir)?;
+        config.save_not_yet_released_marker(Y21, D01, SystemTime::now())?;
+
+        // Not logged in, so trying to download anyway would fail.
+        let puzzles = vec![
+            (solver!(Y21, D01, mock_solve, mock_solve), Parts::First),
+            (solver!(Y21, D02, mock_solve, mock_solve), Parts::Both),
+        ];
+
+        let (_tx_shutdown, rx_shutdown) = watch::channel(false);
+        let (tx_next, mut rx_next) = mpsc::channel(10);
+        let (tx_ui, mut rx_ui) = mpsc::channel(10);
+
+        run(config, puzzles, false, rx_shutdown, tx_next, tx_ui).await;
+
+        let mut events = vec![];
+        while let Some(event) = rx_ui.recv().await {
+            let state = match event.state {
+                State::Skipped => "skipped",
+                State::Unavailable(reason) => reason,
+                State::Started(_) => "started",
+                State::Done(_, Err(_)) => "failed",
+                others => panic!("Unexpected state: {others:?}"),
+            };
+            events.push((event.day, event.step, state));
+        }
+
+        assert_eq!(events, vec![
+            (D01, Step::Download, "puzzle has not been released yet"),
+            (D01, Step::Preproc, "skipped"),
+            (D01, Step::Part1, "skipped"),
+            (D02, Step::Download, "started"),
+            (D02, Step::Download, "failed"),
+        ]);
+        assert!(rx_next.recv().await.is_none());
+
+        Ok(())
+    }
+
+    #[test_case(Duration::ZERO, &[3, 5, 6]; "each chunk")]
+    #[test_case(Duration::from_secs(3600), &[3]; "throttled")]
+    #[tokio::test]
+    async fn receive_with_progress_sends_events(
+        min_interval: Duration,
+        expected: &[usize],
+    ) -> Result<()> {
+        let chunks = ["1,2", ",3", "\n"].map(Ok::<_, Error>);
+        let chunks = futures::stream::iter(chunks);
+        let (tx, mut rx) = mpsc::channel(10);
+
+        let start_time = Instant::now();
+        let input = receive_with_progress(
+            Y21,
+            D01,
+            start_time,
+            min_interval,
+            chunks,
+            &tx,
+        )
+        .await?;
+        drop(tx);
+
+        assert_eq!(input, "1,2,3\n");
+
+        let mut progress = vec![];
+        while let Some(event) = rx.recv().await {
+            match event {
+                Event {
+                    year: Y21,
+                    day: D01,
+                    step: Step::Download,
+                    state: State::Progress(t, bytes),
+                } if t == start_time => progress.push(bytes),
+                others => panic!("Unexpected event: {others:?}"),
+            }
+        }
+
+        assert_eq!(progress, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn receive_with_progress_fails_on_broken_chunk() {
+        let chunks: [Result<&str>; 2] =
+            [Ok("1,2"), Err(err!("Connection reset"))];
+        let chunks = futures::stream::iter(chunks);
+        let (tx, _rx) = mpsc::channel(10);
+
+        let result = receive_with_progress(
+            Y21,
+            D01,
+            Instant::now(),
+            Duration::ZERO,
+            chunks,
+            &tx,
+        )
+        .await;
+        let msg = format!("{:#}", result.unwrap_err());
+
+        assert!(msg.contains("Failed to receive input"));
+        assert!(msg.contains("Connection reset"));
+    }
+}
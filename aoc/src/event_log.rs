@@ -0,0 +1,353 @@
+use std::io::Write;
+
+use tokio::{
+    sync::{mpsc, watch},
+    task,
+};
+
+use crate::{
+    runner::QueueDepth,
+    solver::{num_threads, Event, State},
+};
+
+/// Sits between the other actors and the UI, writing each [`Event`]
+/// as a line of JSON before forwarding the event to the UI.
+/// Also writes a line each time the [`QueueDepth`] of the runner changes.
+///
+/// Don't write the log to the terminal while the UI is open,
+/// because the log would garble the screen.
+///
+/// This is a debugging aid for the actor pipeline.
+/// The JSON format is deliberately simple and not meant to be stable.
+pub struct EventLog {
+    tx: mpsc::Sender<Event>,
+}
+
+impl EventLog {
+    pub fn spawn<W>(
+        tx_ui: mpsc::Sender<Event>,
+        rx_queue: watch::Receiver<QueueDepth>,
+        w: W,
+    ) -> Self
+    where
+        W: Write + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(num_threads());
+        task::spawn(run_actor(rx, tx_ui, rx_queue, w));
+        Self { tx }
+    }
+
+    pub fn tx(&self) -> mpsc::Sender<Event> {
+        self.tx.clone()
+    }
+}
+
+async fn run_actor<W: Write>(
+    mut rx: mpsc::Receiver<Event>,
+    tx_ui: mpsc::Sender<Event>,
+    mut rx_queue: watch::Receiver<QueueDepth>,
+    mut w: W,
+) {
+    // Failing to write the log should not break the pipeline,
+    // so write errors are ignored below.
+    loop {
+        tokio::select! {
+            biased;
+
+            Ok(()) = rx_queue.changed() => {
+                let depth = *rx_queue.borrow_and_update();
+                if let Ok(json) = queue_depth_to_json(depth) {
+                    let _ = writeln!(w, "{json}");
+                }
+            }
+
+            event = rx.recv() => {
+                let Some(event) = event else {
+                    return;
+                };
+
+                if let Ok(json) = to_json(&event) {
+                    let _ = writeln!(w, "{json}");
+                }
+
+                if tx_ui.send(event).await.is_err() {
+                    return; // UI stopped early, e.g. because of `--fail-fast`
+                }
+            }
+        }
+    }
+}
+
+/// A line of the event log. Fields that are `None` are omitted.
+#[derive(serde::Serialize)]
+struct LogLine {
+    year: u16,
+    day: u8,
+    step: String,
+    state: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duration_us: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    answer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// A line of the event log showing the [`QueueDepth`] of the runner.
+#[derive(serde::Serialize)]
+struct QueueLine {
+    queued:    usize,
+    capacity:  usize,
+    saturated: bool,
+}
+
+fn queue_depth_to_json(depth: QueueDepth) -> serde_json::Result<String> {
+    serde_json::to_string(&QueueLine {
+        queued:    depth.queued,
+        capacity:  depth.capacity,
+        saturated: depth.is_saturated(),
+    })
+}
+
+fn to_json(event: &Event) -> serde_json::Result<String> {
+    let Event {
+        year,
+        day,
+        step,
+        state,
+    } = event;
+
+    let mut line = LogLine {
+        year: u16::from(*year),
+        day: u8::from(*day),
+        step: format!("{step:?}"),
+        state: "",
+        reason: None,
+        bytes: None,
+        duration_us: None,
+        answer: None,
+        error: None,
+    };
+
+    match state {
+        State::Waiting => line.state = "Waiting",
+        State::Skipped => line.state = "Skipped",
+        State::Unavailable(reason) => {
+            line.state = "Unavailable";
+            line.reason = Some(reason);
+        }
+        State::Started(_) => line.state = "Started",
+        State::Progress(_, bytes) => {
+            line.state = "Progress";
+            line.bytes = Some(*bytes);
+        }
+        State::Done(t, result) => {
+            line.state = "Done";
+            line.duration_us = Some(t.as_micros());
+            match result {
+                Ok(answer) => {
+                    line.answer = answer.as_ref().map(|a| a.to_string())
+                }
+                Err(e) => line.error = Some(e.to_string()),
+            }
+        }
+    }
+
+    serde_json::to_string(&line)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant},
+    };
+
+    use lazy_errors::{prelude::*, Result};
+    use test_case::test_case;
+
+    use crate::{
+        ident::{day::*, year::*},
+        solver::Step,
+    };
+
+    use super::*;
+
+    /// Cloneable writer that allows us to inspect what has been written.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn log_and_forward_events() -> Result<()> {
+        let buffer = Buffer::default();
+        let (tx_ui, mut rx_ui) = mpsc::channel(10);
+
+        let (_, rx_queue) = watch::channel(QueueDepth::default());
+        let log = EventLog::spawn(tx_ui, rx_queue, buffer.clone());
+        let tx = log.tx();
+        drop(log);
+
+        let t = Duration::from_micros(1234);
+        let events = [
+            (Step::Download, State::Skipped),
+            (Step::Preproc, State::Started(Instant::now())),
+            (Step::Preproc, State::Done(t, Ok(None))),
+            (Step::Part1, State::Done(t, Ok(Some(Box::new(42))))),
+            (Step::Part2, State::Done(t, Err(err!("Mock \"error\"")))),
+        ];
+
+        for (step, state) in events {
+            let event = Event {
+                year: Y21,
+                day: D01,
+                step,
+                state,
+            };
+            tx.send(event).await.unwrap();
+        }
+        drop(tx);
+
+        let mut forwarded = vec![];
+        while let Some(event) = rx_ui.recv().await {
+            forwarded.push(event.step);
+        }
+
+        assert_eq!(forwarded, vec![
+            Step::Download,
+            Step::Preproc,
+            Step::Preproc,
+            Step::Part1,
+            Step::Part2,
+        ]);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone())
+            .or_wrap_with(|| "Output is not UTF-8")?;
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines, vec![
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Download","#,
+                r#""state":"Skipped"}"#
+            ),
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Preproc","#,
+                r#""state":"Started"}"#
+            ),
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Preproc","#,
+                r#""state":"Done","duration_us":1234}"#
+            ),
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Part1","#,
+                r#""state":"Done","duration_us":1234,"answer":"42"}"#
+            ),
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Part2","#,
+                r#""state":"Done","duration_us":1234,"#,
+                r#""error":"Mock \"error\""}"#
+            ),
+        ]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn log_queue_depth_changes() -> Result<()> {
+        let buffer = Buffer::default();
+        let (tx_ui, mut rx_ui) = mpsc::channel(10);
+        let (tx_queue, rx_queue) = watch::channel(QueueDepth::default());
+
+        let log = EventLog::spawn(tx_ui, rx_queue, buffer.clone());
+        let tx = log.tx();
+        drop(log);
+
+        for queued in [1, 3] {
+            tx_queue.send_replace(QueueDepth {
+                queued,
+                capacity: 3,
+            });
+
+            let event = Event {
+                year:  Y21,
+                day:   D01,
+                step:  Step::Download,
+                state: State::Skipped,
+            };
+            tx.send(event).await.unwrap();
+            rx_ui.recv().await.unwrap();
+        }
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone())
+            .or_wrap_with(|| "Output is not UTF-8")?;
+        let lines: Vec<&str> = output
+            .lines()
+            .filter(|line| !line.contains("\"step\""))
+            .collect();
+
+        assert_eq!(lines, vec![
+            r#"{"queued":1,"capacity":3,"saturated":false}"#,
+            r#"{"queued":3,"capacity":3,"saturated":true}"#,
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn to_json_progress() -> Result<()> {
+        let event = Event {
+            year:  Y24,
+            day:   D16,
+            step:  Step::Download,
+            state: State::Progress(Instant::now(), 1337),
+        };
+
+        assert_eq!(
+            to_json(&event).or_wrap()?,
+            concat!(
+                r#"{"year":2024,"day":16,"step":"Download","#,
+                r#""state":"Progress","bytes":1337}"#
+            )
+        );
+        Ok(())
+    }
+
+    #[test_case("", r#""""#; "empty")]
+    #[test_case("42", r#""42""#; "plain")]
+    #[test_case("a\"b", r#""a\"b""#; "quote")]
+    #[test_case("a\\b", r#""a\\b""#; "backslash")]
+    #[test_case("a\nb\tc", r#""a\nb\tc""#; "whitespace")]
+    #[test_case("\u{1}", r#""\u0001""#; "control character")]
+    fn to_json_escapes_answer(answer: &str, expected: &str) -> Result<()> {
+        let t = Duration::from_micros(1);
+        let event = Event {
+            year:  Y21,
+            day:   D01,
+            step:  Step::Part1,
+            state: State::Done(t, Ok(Some(Box::new(answer.to_owned())))),
+        };
+
+        let expected = format!(
+            "{}{expected}}}",
+            concat!(
+                r#"{"year":2021,"day":1,"step":"Part1","#,
+                r#""state":"Done","duration_us":1,"answer":"#
+            )
+        );
+        assert_eq!(to_json(&event).or_wrap()?, expected);
+        Ok(())
+    }
+}
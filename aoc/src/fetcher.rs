@@ -0,0 +1,74 @@
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+
+/// Why adventofcode.com did not respond successfully to a request.
+#[derive(thiserror::Error, Debug, Copy, Clone, PartialEq, Hash, Eq)]
+pub enum FetchError {
+    #[error("Page not found")]
+    NotFound,
+
+    // adventofcode.com sends HTTP 400 instead of HTTP 401,
+    // so we can't distinguish “real” errors.
+    #[error("HTTP request failed. Are you logged in?")]
+    Failed,
+}
+
+/// Sends a GET request to `url`, authenticated by `session_cookie`.
+pub async fn get(
+    url: &str,
+    session_cookie: &str,
+) -> Result<Response, FetchError> {
+    let request = reqwest::Client::new().request(Method::GET, url);
+    send(request, session_cookie).await
+}
+
+/// Sends `form` in a POST request to `url`,
+/// authenticated by `session_cookie`.
+pub async fn post(
+    url: &str,
+    session_cookie: &str,
+    form: &[(&str, String)],
+) -> Result<Response, FetchError> {
+    let request = reqwest::Client::new()
+        .request(Method::POST, url)
+        .form(form);
+    send(request, session_cookie).await
+}
+
+async fn send(
+    request: RequestBuilder,
+    session_cookie: &str,
+) -> Result<Response, FetchError> {
+    let response = request
+        .header("Cookie", format!("session={session_cookie}"))
+        .send()
+        .await
+        .map_err(|_| FetchError::Failed)?;
+
+    check_status(response.status())?;
+    Ok(response)
+}
+
+fn check_status(status: StatusCode) -> Result<(), FetchError> {
+    match status {
+        StatusCode::NOT_FOUND => Err(FetchError::NotFound),
+        s if s.is_client_error() || s.is_server_error() => {
+            Err(FetchError::Failed)
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(StatusCode::OK, Ok(()))]
+    #[test_case(StatusCode::NOT_FOUND, Err(FetchError::NotFound))]
+    #[test_case(StatusCode::BAD_REQUEST, Err(FetchError::Failed))]
+    #[test_case(StatusCode::INTERNAL_SERVER_ERROR, Err(FetchError::Failed))]
+    fn check_status(status: StatusCode, expected: Result<(), FetchError>) {
+        assert_eq!(super::check_status(status), expected);
+    }
+}
@@ -1,10 +1,12 @@
 use std::{
+    ffi::OsStr,
     fmt::Debug,
     fs::File,
     io::{BufRead, BufReader, Read},
     path::{Path, PathBuf},
     process::Command,
     str,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
 use lazy_errors::{prelude::*, Result};
@@ -14,7 +16,7 @@ use crate::ident::{Day, Id, Year};
 #[cfg(test)]
 use crate::ident::Part;
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 use tempfile::TempDir;
 
 const APP_SUBDIR_NAME: &str = "advent_of_code";
@@ -74,7 +76,7 @@ impl AsRef<Path> for RepoDir {
 }
 
 impl Config {
-    #[cfg(test)]
+    #[cfg(any(test, feature = "testing"))]
     pub fn from(
         repo_dir: RepoDir,
         data_dir: DataDir,
@@ -89,6 +91,22 @@ impl Config {
         }
     }
 
+    pub fn repo_dir(&self) -> &Path {
+        &self.repo_dir.path
+    }
+
+    pub fn data_dir(&self) -> &Path {
+        &self.data_dir.path
+    }
+
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir.path
+    }
+
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir.path
+    }
+
     /// Reads all required environment variables and uses defaults if missing.
     pub fn from_env_or_defaults() -> Result<Self> {
         Ok(Self {
@@ -99,6 +117,15 @@ impl Config {
         })
     }
 
+    /// Uses the local instead of the roaming data directory on Windows.
+    /// Has no effect on other platforms.
+    pub fn with_local_data_dir(self) -> Result<Self> {
+        Ok(Self {
+            data_dir: DataDir::new(true)?,
+            ..self
+        })
+    }
+
     pub fn save_session_cookie(&mut self, cookie: &str) -> Result<()> {
         self.config_dir
             .save_session_cookie(cookie)
@@ -108,10 +135,21 @@ impl Config {
         self.config_dir.read_session_cookie()
     }
 
+    /// Like [`Config::read_session_cookie`],
+    /// but fails if the session cookie has not been saved.
+    pub fn require_session_cookie(&self) -> Result<String> {
+        self.read_session_cookie()?
+            .ok_or_else(|| err!("Not logged in"))
+    }
+
     pub fn delete_session_cookie(&mut self) -> Result<()> {
         self.config_dir.delete_session_cookie()
     }
 
+    pub fn session_cookie_path(&self) -> PathBuf {
+        self.config_dir.session_cookie_path()
+    }
+
     pub fn save_personal_puzzle_input(
         &mut self,
         y: Year,
@@ -136,16 +174,119 @@ impl Config {
             .personal_puzzle_inputs_dir()
     }
 
+    pub fn personal_puzzle_input_modified(
+        &self,
+        y: Year,
+        d: Day,
+    ) -> Result<Option<SystemTime>> {
+        self.cache_dir
+            .personal_puzzle_input_modified(y, d)
+    }
+
+    pub fn save_not_yet_released_marker(
+        &mut self,
+        y: Year,
+        d: Day,
+        now: SystemTime,
+    ) -> Result<()> {
+        self.cache_dir
+            .save_not_yet_released_marker(y, d, now)
+    }
+
+    pub fn is_marked_not_yet_released(
+        &self,
+        y: Year,
+        d: Day,
+        now: SystemTime,
+    ) -> Result<bool> {
+        self.cache_dir
+            .is_marked_not_yet_released(y, d, now)
+    }
+
+    /// Makes this config read and save personal puzzle inputs in `dir`
+    /// instead of in the cache directory.
+    ///
+    /// Fails if `dir` is not an existing directory.
+    pub fn with_personal_puzzle_inputs_dir(
+        mut self,
+        dir: &Path,
+    ) -> Result<Self> {
+        self.cache_dir = self
+            .cache_dir
+            .with_personal_puzzle_inputs_dir(dir)?;
+        Ok(self)
+    }
+
+    /// Fails if the directory that personal puzzle inputs are kept in
+    /// is not an existing directory, e.g. if `AOC_INPUT_DIR` is invalid.
+    pub fn check_personal_puzzle_inputs_dir(&self) -> Result<()> {
+        self.cache_dir
+            .check_personal_puzzle_inputs_dir()
+    }
+
+    /// Returns how many personal puzzle inputs have been downloaded
+    /// or put into the cache directory manually.
+    pub fn count_personal_puzzle_inputs(&self) -> Result<usize> {
+        count_files_with_suffix(
+            self.personal_puzzle_inputs_dir(),
+            "_personal_puzzle_input.txt",
+        )
+    }
+
+    /// Returns how many personal puzzle inputs are in the cache
+    /// and how many bytes they take up in total.
+    pub fn size_of_personal_puzzle_inputs(&self) -> Result<(usize, u64)> {
+        size_of_files_with_suffix(
+            self.personal_puzzle_inputs_dir(),
+            "_personal_puzzle_input.txt",
+        )
+    }
+
+    /// Deletes all personal puzzle inputs and other files
+    /// in the cache directory, returning how many files were deleted.
+    /// Keeps the directories and the session cookie.
+    ///
+    /// Personal puzzle inputs in a directory set via
+    /// [`Config::with_personal_puzzle_inputs_dir`] or `AOC_INPUT_DIR`
+    /// are managed by the user, not cached, and will be kept.
+    pub fn clear_all_caches(&mut self) -> Result<usize> {
+        self.cache_dir.clear_all()
+    }
+
+    /// Returns where personal puzzle inputs are cached
+    /// if no other directory is set for them.
+    pub fn cached_personal_puzzle_inputs_dir(&self) -> PathBuf {
+        self.cache_dir
+            .cached_personal_puzzle_inputs_dir()
+    }
+
     pub fn personal_leaderboard_file(&self, y: Year) -> PathBuf {
         self.data_dir
             .personal_leaderboard_file(y)
     }
 
+    pub fn personal_leaderboard_json_file(&self, y: Year) -> PathBuf {
+        self.data_dir
+            .personal_leaderboard_json_file(y)
+    }
+
     pub fn personal_leaderboard_dir(&self) -> PathBuf {
         self.data_dir.personal_leaderboard_dir()
     }
 
-    #[cfg(test)]
+    /// Returns how many personal leaderboard files are in the data directory.
+    pub fn count_personal_leaderboards(&self) -> Result<usize> {
+        let txt = count_files_with_suffix(
+            self.personal_leaderboard_dir(),
+            "_personal_leaderboard_statistics.txt",
+        )?;
+        let json = count_files_with_suffix(
+            self.personal_leaderboard_dir(),
+            "_personal_leaderboard_statistics.json",
+        )?;
+        Ok(txt + json)
+    }
+
     pub fn read_example_puzzle_input(
         &self,
         y: Year,
@@ -166,6 +307,25 @@ impl Config {
         self.data_dir
             .personal_puzzle_answer(y, d, p)
     }
+
+    pub fn read_example_answers(
+        &self,
+        y: Year,
+        d: Day,
+        label: &str,
+    ) -> Result<(Option<String>, Option<String>)> {
+        self.repo_dir
+            .read_example_answers(y, d, label)
+    }
+
+    pub fn example_answers_labels(
+        &self,
+        y: Year,
+        d: Day,
+    ) -> Result<Vec<String>> {
+        self.repo_dir
+            .example_answers_labels(y, d)
+    }
 }
 
 impl RepoDir {
@@ -180,7 +340,6 @@ impl RepoDir {
         Self::try_from(path.as_path())
     }
 
-    #[cfg(test)]
     pub fn read_personal_puzzle_input(
         &self,
         y: Year,
@@ -195,7 +354,67 @@ impl RepoDir {
             })
     }
 
-    #[cfg(test)]
+    /// Reads the expected answers to an example puzzle input.
+    ///
+    /// The answers file is located next to the example puzzle input file.
+    /// Its first line is the answer to part one,
+    /// its second line is the answer to part two.
+    /// If a line is missing, empty, or `-`, there is no answer to that part
+    /// (e.g. because the example input only applies to the other part).
+    pub fn read_example_answers(
+        &self,
+        y: Year,
+        d: Day,
+        label: &str,
+    ) -> Result<(Option<String>, Option<String>)> {
+        let id = Id((y, d));
+
+        let mut path = self.example_puzzle_inputs_dir();
+        path.push(format!("{id}_example_puzzle_answers_{label}.txt"));
+
+        read_to_string(path)
+            .map(|text| parse_example_answers(&text))
+            .or_wrap_with(|| {
+                format!("Failed to read {id} example puzzle answers '{label}'")
+            })
+    }
+
+    /// Returns the labels of all example puzzle inputs of `y` and `d`
+    /// for which there is a file containing the expected answers,
+    /// in lexicographical order.
+    pub fn example_answers_labels(
+        &self,
+        y: Year,
+        d: Day,
+    ) -> Result<Vec<String>> {
+        let dir = self.example_puzzle_inputs_dir();
+        let prefix = format!("{}_example_puzzle_answers_", Id((y, d)));
+
+        let entries = std::fs::read_dir(&dir).or_wrap_with(|| {
+            format!("Failed to read directory '{}'", dir.display())
+        })?;
+
+        let mut labels = vec![];
+        for entry in entries {
+            let entry = entry.or_wrap_with(|| {
+                format!("Failed to read directory '{}'", dir.display())
+            })?;
+
+            let name = entry.file_name();
+            let label = name
+                .to_str()
+                .and_then(|name| name.strip_prefix(&prefix))
+                .and_then(|name| name.strip_suffix(".txt"));
+
+            if let Some(label) = label {
+                labels.push(label.to_owned());
+            }
+        }
+
+        labels.sort();
+        Ok(labels)
+    }
+
     pub fn example_puzzle_input_file(
         &self,
         y: Year,
@@ -204,15 +423,18 @@ impl RepoDir {
     ) -> Result<PathBuf> {
         let id = Id((y, d));
 
-        let mut path = self.path.clone();
-        path.push(format!(
-            "aoc/example_puzzle_inputs/\
-            {id}_example_puzzle_input_{label}.txt"
-        ));
+        let mut path = self.example_puzzle_inputs_dir();
+        path.push(format!("{id}_example_puzzle_input_{label}.txt"));
 
         Ok(path)
     }
 
+    fn example_puzzle_inputs_dir(&self) -> PathBuf {
+        let mut path = self.path.clone();
+        path.push("aoc/example_puzzle_inputs");
+        path
+    }
+
     /// [As of 2023-05-27, the `CARGO_WORKSPACE_DIR` environment variable
     /// is still a WIP.][1]
     /// Thus, for the time being, this function determines the correct value.
@@ -250,13 +472,30 @@ impl RepoDir {
 }
 
 impl DataDir {
+    const LOCAL_DATA_ENV_VAR: &'static str = "AOC_USE_LOCAL_DATA";
+
+    /// Uses the roaming data directory on Windows,
+    /// unless the `AOC_USE_LOCAL_DATA` environment variable is enabled.
     pub fn from_env() -> Result<Self> {
-        match dirs::data_dir() {
+        let value = std::env::var_os(Self::LOCAL_DATA_ENV_VAR);
+        Self::new(is_enabled(value.as_deref()))
+    }
+
+    /// Uses the local instead of the roaming data directory if `local` is set.
+    /// Both are the same on all platforms but Windows.
+    pub fn new(local: bool) -> Result<Self> {
+        let (dir, kind) = if local {
+            (dirs::data_local_dir(), "local data")
+        } else {
+            (dirs::data_dir(), "user data")
+        };
+
+        match dir {
             Some(mut path) => {
                 path.push(APP_SUBDIR_NAME);
                 Ok(Self { path })
             }
-            None => Err(err!("Failed to determine user data directory")),
+            None => Err(err!("Failed to determine {kind} directory")),
         }
     }
 
@@ -269,6 +508,15 @@ impl DataDir {
         path
     }
 
+    pub fn personal_leaderboard_json_file(&self, y: Year) -> PathBuf {
+        let y = Id(y);
+
+        let mut path = self.personal_leaderboard_dir();
+        path.push(format!("{y}_personal_leaderboard_statistics.json"));
+
+        path
+    }
+
     pub fn personal_leaderboard_dir(&self) -> PathBuf {
         let mut path = self.path.clone();
         path.push(LEADERBOARD_SUBDIR_NAME);
@@ -316,12 +564,12 @@ impl ConfigDir {
     }
 
     pub fn save_session_cookie(&mut self, cookie: &str) -> Result<()> {
-        write(self.session_cookie_file(), cookie)
+        write(self.session_cookie_path(), cookie)
             .or_wrap_with(|| "Failed to save session cookie")
     }
 
     pub fn read_session_cookie(&self) -> Result<Option<String>> {
-        let path = self.session_cookie_file();
+        let path = self.session_cookie_path();
         if !path.exists() {
             return Ok(None);
         }
@@ -332,11 +580,12 @@ impl ConfigDir {
     }
 
     pub fn delete_session_cookie(&mut self) -> Result<()> {
-        delete(self.session_cookie_file())
+        delete(self.session_cookie_path())
             .or_wrap_with(|| "Failed to delete session cookie")
     }
 
-    fn session_cookie_file(&self) -> PathBuf {
+    /// Returns where the session cookie is saved (if the user logged in).
+    pub fn session_cookie_path(&self) -> PathBuf {
         let mut path = self.path.clone();
         path.push("session.cookie");
         path
@@ -344,14 +593,35 @@ impl ConfigDir {
 }
 
 impl CacheDir {
+    const INPUT_DIR_ENV_VAR: &'static str = "AOC_INPUT_DIR";
+    /// How long we trust adventofcode.com telling us that a puzzle
+    /// has not been released yet before asking again.
+    const NOT_YET_RELEASED_TTL: Duration = Duration::from_secs(10 * 60);
+    const PERSONAL_PUZZLE_INPUTS_SUBDIR: &'static str =
+        "personal_puzzle_inputs";
+
     /// Creates the directory if it does not exist.
+    ///
+    /// If the `AOC_INPUT_DIR` environment variable is set,
+    /// personal puzzle inputs will be kept in that directory instead.
+    /// That directory is not checked here, so that commands like
+    /// `doctor` still work if it is invalid
+    /// (see [`CacheDir::check_personal_puzzle_inputs_dir`]).
     pub fn from_env() -> Result<Self> {
-        match dirs::cache_dir() {
+        let cache_dir = match dirs::cache_dir() {
             Some(mut path) => {
                 path.push(APP_SUBDIR_NAME);
-                Self::new(&path)
+                Self::new(&path)?
             }
-            None => Err(err!("Failed to determine cache directory")),
+            None => return Err(err!("Failed to determine cache directory")),
+        };
+
+        match env_var_dir_check(Self::INPUT_DIR_ENV_VAR)? {
+            Some(dir) => Ok(Self {
+                personal_puzzle_inputs_dir: dir,
+                ..cache_dir
+            }),
+            None => Ok(cache_dir),
         }
     }
 
@@ -361,8 +631,8 @@ impl CacheDir {
         create_dir_all(&path)
             .or_wrap_with(|| "Failed to create cache directory")?;
 
-        let mut personal_puzzle_inputs_dir = path.clone();
-        personal_puzzle_inputs_dir.push("personal_puzzle_inputs");
+        let personal_puzzle_inputs_dir =
+            path.join(Self::PERSONAL_PUZZLE_INPUTS_SUBDIR);
 
         create_dir_all(&personal_puzzle_inputs_dir).or_wrap_with(|| {
             "Failed to create personal puzzle inputs directory"
@@ -374,6 +644,13 @@ impl CacheDir {
         })
     }
 
+    /// Returns where personal puzzle inputs are cached,
+    /// regardless of [`CacheDir::with_personal_puzzle_inputs_dir`].
+    pub fn cached_personal_puzzle_inputs_dir(&self) -> PathBuf {
+        self.path
+            .join(Self::PERSONAL_PUZZLE_INPUTS_SUBDIR)
+    }
+
     pub fn save_personal_puzzle_input(
         &mut self,
         y: Year,
@@ -382,7 +659,9 @@ impl CacheDir {
     ) -> Result<()> {
         let path = self.personal_puzzle_input_file(y, d);
         write(path, input)
-            .or_wrap_with(|| "Failed to save personal puzzle input")
+            .or_wrap_with(|| "Failed to save personal puzzle input")?;
+
+        delete(self.not_yet_released_marker_file(y, d))
     }
 
     pub fn read_personal_puzzle_input(
@@ -401,10 +680,114 @@ impl CacheDir {
             .or_wrap_with(|| "Failed to read personal puzzle input")
     }
 
+    /// Returns when the personal puzzle input was modified last,
+    /// or `None` if there is no such file.
+    pub fn personal_puzzle_input_modified(
+        &self,
+        y: Year,
+        d: Day,
+    ) -> Result<Option<SystemTime>> {
+        let path = self.personal_puzzle_input_file(y, d);
+
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .map(Some)
+            .or_wrap_with(|| "Failed to read modification time of input")
+    }
+
     pub fn personal_puzzle_inputs_dir(&self) -> PathBuf {
         self.personal_puzzle_inputs_dir.clone()
     }
 
+    /// Fails if `dir` is not an existing directory.
+    pub fn with_personal_puzzle_inputs_dir(self, dir: &Path) -> Result<Self> {
+        let cache_dir = Self {
+            personal_puzzle_inputs_dir: dir.to_path_buf(),
+            ..self
+        };
+
+        cache_dir.check_personal_puzzle_inputs_dir()?;
+        Ok(cache_dir)
+    }
+
+    /// Fails if the directory that personal puzzle inputs are kept in
+    /// is not an existing directory.
+    pub fn check_personal_puzzle_inputs_dir(&self) -> Result<()> {
+        from_path_if_dir(&self.personal_puzzle_inputs_dir)
+            .or_wrap_with(|| "Invalid personal puzzle inputs directory")?;
+        Ok(())
+    }
+
+    /// Remembers that adventofcode.com responded with HTTP 404
+    /// when we tried to download the puzzle input at `now`.
+    pub fn save_not_yet_released_marker(
+        &mut self,
+        y: Year,
+        d: Day,
+        now: SystemTime,
+    ) -> Result<()> {
+        let secs = now
+            .duration_since(UNIX_EPOCH)
+            .or_wrap_with(|| "System time is before the UNIX epoch")?
+            .as_secs();
+
+        let path = self.not_yet_released_marker_file(y, d);
+        write(path, secs.to_string())
+            .or_wrap_with(|| "Failed to save not-yet-released marker")
+    }
+
+    /// Returns whether there is a marker saved by
+    /// [`CacheDir::save_not_yet_released_marker`]
+    /// that has not expired yet at `now`.
+    ///
+    /// Markers that cannot be parsed are treated as expired.
+    pub fn is_marked_not_yet_released(
+        &self,
+        y: Year,
+        d: Day,
+        now: SystemTime,
+    ) -> Result<bool> {
+        let path = self.not_yet_released_marker_file(y, d);
+
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let text = read_to_string(path)
+            .or_wrap_with(|| "Failed to read not-yet-released marker")?;
+
+        let Ok(secs) = text.trim().parse::<u64>() else {
+            return Ok(false);
+        };
+
+        let marked_at = UNIX_EPOCH + Duration::from_secs(secs);
+        Ok(now < marked_at + Self::NOT_YET_RELEASED_TTL)
+    }
+
+    /// Deletes all cached personal puzzle inputs
+    /// and not-yet-released markers, returning how many files were deleted.
+    /// The directories themselves are kept, as are the personal puzzle
+    /// inputs in the directory set by
+    /// [`CacheDir::with_personal_puzzle_inputs_dir`].
+    pub fn clear_all(&mut self) -> Result<usize> {
+        let inputs = delete_files_with_suffix(
+            self.cached_personal_puzzle_inputs_dir(),
+            "_personal_puzzle_input.txt",
+        )?;
+        let markers = delete_files_with_suffix(&self.path, ".notyet")?;
+        Ok(inputs + markers)
+    }
+
+    fn not_yet_released_marker_file(&self, y: Year, d: Day) -> PathBuf {
+        let mut path = self.path.clone();
+        path.push(format!("{}.notyet", Id((y, d))));
+        path
+    }
+
     fn personal_puzzle_input_file(&self, y: Year, d: Day) -> PathBuf {
         let mut path = self.personal_puzzle_inputs_dir();
         path.push(format!("{}_personal_puzzle_input.txt", Id((y, d))));
@@ -473,6 +856,113 @@ where
         .or_wrap_with(|| format!("Failed to delete '{}'", path.display()))
 }
 
+/// Returns whether `dir` is a directory we can create files in,
+/// by creating and deleting a temporary file there.
+pub fn is_writable_dir<P>(dir: P) -> bool
+where
+    P: AsRef<Path>,
+{
+    let mut path = dir.as_ref().to_path_buf();
+    if !path.is_dir() {
+        return false;
+    }
+
+    path.push(".aoc_write_test");
+    write(&path, "").is_ok() && delete(&path).is_ok()
+}
+
+/// Counts the files in `dir` whose name ends with `suffix`.
+/// If `dir` does not exist, there are no such files.
+pub fn count_files_with_suffix<P>(dir: P, suffix: &str) -> Result<usize>
+where
+    P: AsRef<Path>,
+{
+    let (count, _) = size_of_files_with_suffix(dir, suffix)?;
+    Ok(count)
+}
+
+/// Returns how many files in `dir` have names ending in `suffix`
+/// and the sum of their sizes in bytes.
+///
+/// Returns zero for both if `dir` does not exist.
+pub fn size_of_files_with_suffix<P>(
+    dir: P,
+    suffix: &str,
+) -> Result<(usize, u64)>
+where
+    P: AsRef<Path>,
+{
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok((0, 0));
+    }
+
+    let entries = std::fs::read_dir(dir).or_wrap_with(|| {
+        format!("Failed to read directory '{}'", dir.display())
+    })?;
+
+    let mut count = 0;
+    let mut bytes = 0;
+    for entry in entries {
+        let entry = entry.or_wrap_with(|| {
+            format!("Failed to read directory '{}'", dir.display())
+        })?;
+
+        let name = entry.file_name();
+        if name
+            .to_str()
+            .is_some_and(|name| name.ends_with(suffix))
+        {
+            let metadata = entry.metadata().or_wrap_with(|| {
+                let path = entry.path();
+                format!("Failed to read metadata of '{}'", path.display())
+            })?;
+
+            count += 1;
+            bytes += metadata.len();
+        }
+    }
+
+    Ok((count, bytes))
+}
+
+/// Deletes the files in `dir` whose name ends with `suffix`
+/// and returns how many files were deleted.
+/// If `dir` does not exist, there are no such files.
+pub fn delete_files_with_suffix<P>(dir: P, suffix: &str) -> Result<usize>
+where
+    P: AsRef<Path>,
+{
+    let dir = dir.as_ref();
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .and_then(|entries| {
+            entries
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect()
+        })
+        .or_wrap_with(|| {
+            format!("Failed to read directory '{}'", dir.display())
+        })?;
+
+    let mut count = 0;
+    for path in paths {
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(suffix))
+        {
+            delete(&path)?;
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
 pub fn lines(reader: BufReader<File>) -> impl Iterator<Item = Result<String>> {
     reader
         .lines()
@@ -490,6 +980,16 @@ fn env_var_dir_check(var: &str) -> Result<Option<PathBuf>> {
     }
 }
 
+/// Treats unset, empty, `0`, and `false` (ignoring case) as disabled.
+fn is_enabled(value: Option<&OsStr>) -> bool {
+    match value.and_then(OsStr::to_str) {
+        Some(v) => {
+            !(v.is_empty() || v == "0" || v.eq_ignore_ascii_case("false"))
+        }
+        None => value.is_some(),
+    }
+}
+
 fn from_path_if_dir(p: &Path) -> Result<PathBuf> {
     if !p.is_dir() {
         return Err(err!("Not a directory: '{}'", p.display()));
@@ -498,13 +998,26 @@ fn from_path_if_dir(p: &Path) -> Result<PathBuf> {
     Ok(p.to_path_buf())
 }
 
+fn parse_example_answers(text: &str) -> (Option<String>, Option<String>) {
+    let mut answers = text
+        .lines()
+        .map(|line| match line.trim() {
+            "" | "-" => None,
+            answer => Some(answer.to_owned()),
+        });
+
+    let p1 = answers.next().flatten();
+    let p2 = answers.next().flatten();
+    (p1, p2)
+}
+
 fn parse_utf8(bytes: &[u8]) -> Result<&str> {
     Ok(str::from_utf8(bytes)
         .or_wrap_with(|| "Cannot create string from bytes")?
         .trim())
 }
 
-#[cfg(test)]
+#[cfg(any(test, feature = "testing"))]
 pub fn tempdir() -> Result<TempDir> {
     tempfile::tempdir().or_wrap_with(|| "Failed to create tempdir")
 }
@@ -514,7 +1027,17 @@ pub fn tempdir() -> Result<TempDir> {
 /// You can pass _a reference to_ a [`tempfile::TempDir`] value,
 /// thereby ensuring it does not go out of scope
 /// since that would delete the corresponding directory on the filesystem.
-#[cfg(test)]
+///
+/// This function is only available in tests
+/// or if the `testing` feature is enabled:
+///
+/// ```
+/// let tempdir = aoc::tempdir()?;
+/// let config = aoc::create_config_for(&tempdir)?;
+/// assert_eq!(config.cache_dir(), tempdir.path());
+/// # Ok::<(), lazy_errors::prelude::Error>(())
+/// ```
+#[cfg(any(test, feature = "testing"))]
 pub fn create_config_for<P>(path: P) -> Result<Config>
 where
     P: AsRef<Path>,
@@ -534,6 +1057,7 @@ mod tests {
     use std::io::Write;
 
     use tempfile::NamedTempFile;
+    use test_case::test_case;
 
     use super::*;
 
@@ -638,6 +1162,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn session_cookie_path() -> Result<()> {
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+
+        let path = config.session_cookie_path();
+        assert!(path.ends_with("session.cookie"));
+        assert_eq!(path.parent(), Some(config.config_dir()));
+
+        config.save_session_cookie("mock cookie")?;
+        assert_eq!(read_to_string(&path)?, "mock cookie");
+
+        Ok(())
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
     fn personal_puzzle_input() -> Result<()> {
@@ -666,6 +1206,158 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn not_yet_released_marker() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+
+        let now = SystemTime::now();
+        let later = now + Duration::from_secs(5 * 60);
+        let expired = now + Duration::from_secs(60 * 60);
+
+        assert!(!config.is_marked_not_yet_released(Y21, D01, now)?);
+
+        config.save_not_yet_released_marker(Y21, D01, now)?;
+        assert!(config.is_marked_not_yet_released(Y21, D01, now)?);
+        assert!(config.is_marked_not_yet_released(Y21, D01, later)?);
+        assert!(!config.is_marked_not_yet_released(Y21, D01, expired)?);
+        assert!(!config.is_marked_not_yet_released(Y21, D02, now)?);
+
+        // Once the input is there, the puzzle obviously has been released.
+        config.save_personal_puzzle_input(Y21, D01, "mock input")?;
+        assert!(!config.is_marked_not_yet_released(Y21, D01, now)?);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn personal_puzzle_input_modified() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+
+        assert_eq!(config.personal_puzzle_input_modified(Y21, D01)?, None);
+
+        config.save_personal_puzzle_input(Y21, D01, "mock input")?;
+        assert!(config
+            .personal_puzzle_input_modified(Y21, D01)?
+            .is_some());
+        assert_eq!(config.personal_puzzle_input_modified(Y21, D02)?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn personal_puzzle_inputs_dir_override() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let input_dir = tempdir()?;
+        let tempdir = tempdir()?;
+        let mut path = input_dir.path().to_path_buf();
+        path.push("y21d01_personal_puzzle_input.txt");
+        write(&path, "pre-placed input")?;
+
+        let config = create_config_for(&tempdir)?;
+        assert!(config
+            .read_personal_puzzle_input(Y21, D01)?
+            .is_none());
+
+        let mut config =
+            config.with_personal_puzzle_inputs_dir(input_dir.path())?;
+        let input = config.read_personal_puzzle_input(Y21, D01)?;
+        assert_eq!(input.unwrap(), "pre-placed input");
+
+        config.save_personal_puzzle_input(Y21, D02, "downloaded input")?;
+        let mut path = input_dir.path().to_path_buf();
+        path.push("y21d02_personal_puzzle_input.txt");
+        assert_eq!(read_to_string(&path)?, "downloaded input");
+
+        let mut missing = input_dir.path().to_path_buf();
+        missing.push("missing");
+        let err = config
+            .with_personal_puzzle_inputs_dir(&missing)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid personal puzzle inputs"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn check_personal_puzzle_inputs_dir() -> Result<()> {
+        let tempdir = tempdir()?;
+        let cache_dir = CacheDir::new(tempdir.path())?;
+        cache_dir.check_personal_puzzle_inputs_dir()?;
+
+        // Like an invalid `AOC_INPUT_DIR`, which `from_env` accepts as is.
+        let cache_dir = CacheDir {
+            personal_puzzle_inputs_dir: tempdir.path().join("missing"),
+            ..cache_dir
+        };
+        let err = cache_dir
+            .check_personal_puzzle_inputs_dir()
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid personal puzzle inputs"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `read_workspace_dir_from_cargo`
+    fn read_example_answers() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let config = Config::from_env_or_defaults()?;
+
+        let answers = config.read_example_answers(Y24, D01, "1")?;
+        assert_eq!(answers, (Some("11".to_owned()), Some("31".to_owned())));
+
+        let err = config
+            .read_example_answers(Y24, D01, "does_not_exist")
+            .unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("y24d01 example puzzle answers 'does_not_exist'"));
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn example_answers_labels() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let tempdir = tempdir()?;
+        let config = create_config_for(&tempdir)?;
+
+        assert_eq!(config.example_answers_labels(Y24, D01)?, vec!["1"]);
+        assert_eq!(config.example_answers_labels(Y24, D03)?, vec!["1", "2"]);
+        assert!(config
+            .example_answers_labels(Y21, D01)?
+            .is_empty());
+
+        Ok(())
+    }
+
+    #[test_case("", None, None)]
+    #[test_case("42\n", Some("42"), None)]
+    #[test_case("42\n1337\n", Some("42"), Some("1337"))]
+    #[test_case("-\n1337\n", None, Some("1337"))]
+    #[test_case("\n1337", None, Some("1337"))]
+    #[test_case(" 42 \r\n-\r\n", Some("42"), None)]
+    fn parse_example_answers(text: &str, p1: Option<&str>, p2: Option<&str>) {
+        let expected = (p1.map(String::from), p2.map(String::from));
+        assert_eq!(super::parse_example_answers(text), expected);
+    }
+
     #[test]
     #[cfg_attr(miri, ignore)] // Because of `tempfile`
     fn open_ok() -> Result<()> {
@@ -767,6 +1459,97 @@ mod tests {
         mktempf(b"Non-UTF contents: \x00\x9F\x92\x96\n")
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn clear_all_caches() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+        let now = SystemTime::now();
+
+        config.save_session_cookie("mock cookie")?;
+        config.save_personal_puzzle_input(Y21, D01, "mock input")?;
+        config.save_personal_puzzle_input(Y21, D02, "mock input")?;
+        config.save_not_yet_released_marker(Y21, D03, now)?;
+
+        let unrelated = config
+            .personal_puzzle_inputs_dir()
+            .join("notes.txt");
+        write(&unrelated, "keep me")?;
+
+        assert_eq!(config.clear_all_caches()?, 3);
+
+        let dir = config.personal_puzzle_inputs_dir();
+        assert!(dir.is_dir());
+        assert_eq!(config.count_personal_puzzle_inputs()?, 0);
+        assert!(!config.is_marked_not_yet_released(Y21, D03, now)?);
+        assert!(unrelated.exists());
+        assert!(config.read_session_cookie()?.is_some());
+
+        assert_eq!(config.clear_all_caches()?, 0);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn clear_all_caches_keeps_inputs_in_other_dir() -> Result<()> {
+        use crate::ident::{day::*, year::*};
+
+        let cache_dir = tempdir()?;
+        let config = create_config_for(&cache_dir)?;
+        let mut cached = config.clone();
+        cached.save_personal_puzzle_input(Y21, D01, "cached input")?;
+
+        let input_dir = tempdir()?;
+        let mut config =
+            config.with_personal_puzzle_inputs_dir(input_dir.path())?;
+        config.save_personal_puzzle_input(Y21, D02, "user input")?;
+        config.save_personal_puzzle_input(Y21, D03, "user input")?;
+
+        assert_eq!(config.clear_all_caches()?, 1);
+        assert_eq!(config.count_personal_puzzle_inputs()?, 2);
+        assert_eq!(cached.count_personal_puzzle_inputs()?, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn data_dir_local_differs_from_roaming_on_windows() -> Result<()> {
+        let roaming = DataDir::new(false)?;
+        let local = DataDir::new(true)?;
+
+        let mut expected = dirs::data_local_dir().unwrap();
+        expected.push(APP_SUBDIR_NAME);
+
+        assert_eq!(local.path, expected);
+        assert_ne!(local.path, roaming.path);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn data_dir_local_equals_roaming_elsewhere() -> Result<()> {
+        let roaming = DataDir::new(false)?;
+        let local = DataDir::new(true)?;
+
+        assert_eq!(local.path, roaming.path);
+        Ok(())
+    }
+
+    #[test_case(None, false; "unset")]
+    #[test_case(Some(""), false; "empty")]
+    #[test_case(Some("0"), false; "zero")]
+    #[test_case(Some("false"), false; "false lowercase")]
+    #[test_case(Some("FALSE"), false; "false uppercase")]
+    #[test_case(Some("1"), true; "one")]
+    #[test_case(Some("true"), true; "true lowercase")]
+    #[test_case(Some("yes"), true; "other value")]
+    fn is_enabled(value: Option<&str>, expected: bool) {
+        assert_eq!(super::is_enabled(value.map(OsStr::new)), expected);
+    }
+
     fn missing_file() -> Result<(TempDir, PathBuf, String)> {
         let dir = tempdir()?;
         let name = String::from("unexisting_file");
@@ -37,6 +37,20 @@ pub const D16: Day = Day(16);
 )]
 pub struct Day(u8);
 
+impl Day {
+    /// Returns the day after `self`, or `None` if `self` is day 25.
+    #[allow(dead_code)]
+    pub fn next(self) -> Option<Self> {
+        Self::try_from(self.0.checked_add(1)?).ok()
+    }
+
+    /// Returns the day before `self`, or `None` if `self` is [`D01`].
+    #[allow(dead_code)]
+    pub fn prev(self) -> Option<Self> {
+        Self::try_from(self.0.checked_sub(1)?).ok()
+    }
+}
+
 impl TryFrom<u8> for Day {
     type Error = Error;
 
@@ -49,6 +63,16 @@ impl TryFrom<u8> for Day {
     }
 }
 
+impl TryFrom<usize> for Day {
+    type Error = Error;
+
+    fn try_from(d: usize) -> Result<Self> {
+        u8::try_from(d)
+            .or_wrap_with(|| format!("Day {d} is out of range [1,25]"))
+            .and_then(Self::try_from)
+    }
+}
+
 impl FromStr for Day {
     type Err = Error;
 
@@ -67,6 +91,21 @@ mod tests {
 
     use super::*;
 
+    #[test_case(D01, Some(D02))]
+    #[test_case(D15, Some(D16))]
+    #[test_case(Day(24), Some(Day(25)))]
+    #[test_case(Day(25), None)]
+    fn next(day: Day, expected: Option<Day>) {
+        assert_eq!(day.next(), expected);
+    }
+
+    #[test_case(D01, None)]
+    #[test_case(D02, Some(D01))]
+    #[test_case(Day(25), Some(Day(24)))]
+    fn prev(day: Day, expected: Option<Day>) {
+        assert_eq!(day.prev(), expected);
+    }
+
     #[test_case(Day(1), "1", 1u8)]
     #[test_case(Day(25), "25", 25u8)]
     fn conversions_ok(day: Day, txt: &str, num: u8) -> Result<()> {
@@ -84,6 +123,21 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(1usize, Day(1))]
+    #[test_case(25usize, Day(25))]
+    fn convert_try_from_usize_ok(num: usize, expected: Day) -> Result<()> {
+        assert_eq!(Day::try_from(num)?, expected);
+        Ok(())
+    }
+
+    #[test_case(0usize)]
+    #[test_case(26usize)]
+    #[test_case(257usize)]
+    fn convert_try_from_usize_err(num: usize) -> Result<()> {
+        let _ = Day::try_from(num).unwrap_err();
+        Ok(())
+    }
+
     #[test_case("")]
     #[test_case("0")]
     #[test_case("26")]
@@ -117,6 +117,14 @@ impl FromStr for FilterTerm {
 }
 
 impl Filter {
+    /// Returns `true` if this filter matches every puzzle,
+    /// i.e. if any of its terms is the all-wildcard [`FilterTerm`].
+    pub fn is_wildcard(&self) -> bool {
+        self.partial_ids
+            .iter()
+            .any(FilterTerm::is_wildcard)
+    }
+
     pub fn matches_year(&self, y: Year) -> bool {
         self.partial_ids
             .iter()
@@ -134,6 +142,14 @@ impl Filter {
             .iter()
             .any(|s| s.matches_year_day_part(y, d, p))
     }
+
+    /// Returns the puzzles named by those terms
+    /// that specify both a [`Year`] and a [`Day`].
+    pub fn year_days(&self) -> impl Iterator<Item = (Year, Day)> + '_ {
+        self.partial_ids
+            .iter()
+            .filter_map(FilterTerm::year_day)
+    }
 }
 
 impl FilterTerm {
@@ -150,6 +166,10 @@ impl FilterTerm {
         }
     }
 
+    pub fn is_wildcard(&self) -> bool {
+        *self == FilterTerm::default()
+    }
+
     pub fn matches_year(&self, y: Year) -> bool {
         matches(&self.year, &y)
     }
@@ -161,6 +181,12 @@ impl FilterTerm {
     pub fn matches_year_day_part(&self, y: Year, d: Day, p: Part) -> bool {
         self.matches_year_day(y, d) && matches(&self.part, &p)
     }
+
+    /// Returns the puzzle this term names,
+    /// unless the year or the day is a wildcard.
+    pub fn year_day(&self) -> Option<(Year, Day)> {
+        Some((self.year?, self.day?))
+    }
 }
 
 fn parse_as_optional_id<T>(s: &str) -> Result<Option<T>>
@@ -239,6 +265,21 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(&[], true; "default")]
+    #[test_case(&["*"], true; "wildcard")]
+    #[test_case(&["y24", "*"], true; "wildcard among others")]
+    #[test_case(&["y24"], false; "year")]
+    #[test_case(&["y24", "d01"], false; "year and day")]
+    fn is_wildcard(terms: &[&str], expected: bool) -> Result<()> {
+        let terms = terms
+            .iter()
+            .map(|t| t.parse())
+            .collect::<Result<Vec<FilterTerm>>>()?;
+
+        assert_eq!(Filter::from(terms).is_wildcard(), expected);
+        Ok(())
+    }
+
     fn from<Y, D, P>(year: Y, day: D, part: P) -> FilterTerm
     where
         Y: Into<Option<u16>>,
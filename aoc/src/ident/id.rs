@@ -167,6 +167,24 @@ impl std::fmt::Display for Id<(Year, Day)> {
     }
 }
 
+const BASE_URL: &str = "https://adventofcode.com";
+
+impl Id<(Year, Day)> {
+    /// Returns the URL to download the puzzle input from.
+    pub fn input_url(&self) -> String {
+        let (y, d) = self.0;
+        format!("{BASE_URL}/{y}/day/{d}/input")
+    }
+}
+
+impl Id<(Year, Day, Part)> {
+    /// Returns the URL to `POST` the answer of this puzzle part to.
+    pub fn answer_url(&self) -> String {
+        let (y, d, _) = self.0;
+        format!("{BASE_URL}/{y}/day/{d}/answer")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
@@ -236,4 +254,20 @@ mod tests {
         let _ = Id::<Year>::from_str(s).unwrap_err();
         Ok(())
     }
+
+    #[test_case("y24d01", "https://adventofcode.com/2024/day/1/input")]
+    #[test_case("y21d25", "https://adventofcode.com/2021/day/25/input")]
+    fn input_url(id: &str, expected: &str) -> Result<()> {
+        let id = Id::<(Year, Day)>::from_str(id)?;
+        assert_eq!(id.input_url(), expected);
+        Ok(())
+    }
+
+    #[test_case("y24d01p1", "https://adventofcode.com/2024/day/1/answer")]
+    #[test_case("y24d01p2", "https://adventofcode.com/2024/day/1/answer")]
+    fn answer_url(id: &str, expected: &str) -> Result<()> {
+        let id = Id::<(Year, Day, Part)>::from_str(id)?;
+        assert_eq!(id.answer_url(), expected);
+        Ok(())
+    }
 }
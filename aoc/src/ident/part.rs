@@ -1,3 +1,5 @@
+use std::fmt;
+
 use lazy_errors::{prelude::*, Result};
 
 pub const P1: Part = Part::Part1;
@@ -17,6 +19,26 @@ pub enum Part {
     Part2,
 }
 
+impl Part {
+    /// Returns [`Part::Part2`] for [`Part::Part1`], or `None` otherwise.
+    #[allow(dead_code)]
+    pub fn next(self) -> Option<Self> {
+        match self {
+            Part::Part1 => Some(Part::Part2),
+            Part::Part2 => None,
+        }
+    }
+
+    /// Returns [`Part::Part1`] for [`Part::Part2`], or `None` otherwise.
+    #[allow(dead_code)]
+    pub fn prev(self) -> Option<Self> {
+        match self {
+            Part::Part1 => None,
+            Part::Part2 => Some(Part::Part1),
+        }
+    }
+}
+
 impl TryFrom<u8> for Part {
     type Error = Error;
 
@@ -29,6 +51,16 @@ impl TryFrom<u8> for Part {
     }
 }
 
+impl TryFrom<usize> for Part {
+    type Error = Error;
+
+    fn try_from(p: usize) -> Result<Self> {
+        u8::try_from(p)
+            .or_wrap_with(|| format!("Puzzle part {p} is out of range [1,2]"))
+            .and_then(Self::try_from)
+    }
+}
+
 impl From<Part> for u8 {
     fn from(value: Part) -> Self {
         match value {
@@ -38,12 +70,25 @@ impl From<Part> for u8 {
     }
 }
 
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&u8::from(*self), f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
 
     use super::*;
 
+    #[test_case(P1, Some(P2), None)]
+    #[test_case(P2, None, Some(P1))]
+    fn next_prev(part: Part, next: Option<Part>, prev: Option<Part>) {
+        assert_eq!(part.next(), next);
+        assert_eq!(part.prev(), prev);
+    }
+
     #[test_case(Part::Part1, 1u8)]
     #[test_case(Part::Part2, 2u8)]
     fn conversions_ok(part: Part, num: u8) -> Result<()> {
@@ -52,6 +97,27 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(1usize, Part::Part1)]
+    #[test_case(2usize, Part::Part2)]
+    fn conversions_usize_ok(num: usize, expected: Part) -> Result<()> {
+        assert_eq!(Part::try_from(num)?, expected);
+        Ok(())
+    }
+
+    #[test_case(0usize)]
+    #[test_case(3usize)]
+    #[test_case(258usize)]
+    fn conversions_usize_err(num: usize) -> Result<()> {
+        let _ = Part::try_from(num).unwrap_err();
+        Ok(())
+    }
+
+    #[test_case(Part::Part1, "1")]
+    #[test_case(Part::Part2, "2")]
+    fn display(part: Part, expected: &str) {
+        assert_eq!(part.to_string(), expected);
+    }
+
     #[test_case(0u8)]
     #[test_case(3u8)]
     fn conversions_err(num: u8) -> Result<()> {
@@ -27,6 +27,22 @@ pub const Y24: Year = Year(2024);
 )]
 pub struct Year(u16);
 
+impl Year {
+    /// Returns the year after `self`,
+    /// or `None` if `self` is the latest supported year.
+    #[allow(dead_code)]
+    pub fn next(self) -> Option<Self> {
+        Self::try_from(self.0.checked_add(1)?).ok()
+    }
+
+    /// Returns the year before `self`,
+    /// or `None` if `self` is the earliest supported year.
+    #[allow(dead_code)]
+    pub fn prev(self) -> Option<Self> {
+        Self::try_from(self.0.checked_sub(1)?).ok()
+    }
+}
+
 impl TryFrom<u16> for Year {
     type Error = Error;
 
@@ -39,12 +55,37 @@ impl TryFrom<u16> for Year {
     }
 }
 
+/// Interprets `y` as a full year, such as `2024`.
+impl TryFrom<usize> for Year {
+    type Error = Error;
+
+    fn try_from(y: usize) -> Result<Self> {
+        u16::try_from(y)
+            .or_wrap_with(|| format!("Year {y} is out of range [2020,2024]"))
+            .and_then(Self::try_from)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
 
     use super::*;
 
+    #[test_case(Year(2020), Some(Y21))]
+    #[test_case(Y23, Some(Y24))]
+    #[test_case(Y24, None)]
+    fn next(year: Year, expected: Option<Year>) {
+        assert_eq!(year.next(), expected);
+    }
+
+    #[test_case(Year(2020), None)]
+    #[test_case(Y21, Some(Year(2020)))]
+    #[test_case(Y24, Some(Y23))]
+    fn prev(year: Year, expected: Option<Year>) {
+        assert_eq!(year.prev(), expected);
+    }
+
     #[test_case(Year(2021), "2021", 2021u16)]
     #[test_case(Year(2024), "2024", 2024u16)]
     fn conversions_ok(year: Year, txt: &str, num: u16) -> Result<()> {
@@ -54,6 +95,21 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(2020usize, Year(2020))]
+    #[test_case(2024usize, Year(2024))]
+    fn conversions_usize_ok(num: usize, expected: Year) -> Result<()> {
+        assert_eq!(Year::try_from(num)?, expected);
+        Ok(())
+    }
+
+    #[test_case(24usize)]
+    #[test_case(2025usize)]
+    #[test_case(67_560usize)]
+    fn conversions_usize_err(num: usize) -> Result<()> {
+        let _ = Year::try_from(num).unwrap_err();
+        Ok(())
+    }
+
     #[test_case(2019u16)]
     #[test_case(2025u16)]
     fn conversions_err(num: u16) -> Result<()> {
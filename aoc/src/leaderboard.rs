@@ -7,11 +7,18 @@ mod stats;
 mod time;
 mod totals;
 
+use std::cmp::Ordering;
+
+pub use formatting::Layout;
 pub use parsing::parse_leaderboards_from_fs;
 
 use crate::{
-    ident::{Day, Year},
-    leaderboard::{formatting::Widths, stats::Stats, totals::Totals},
+    ident::{Day, Part, Year},
+    leaderboard::{
+        formatting::{Widths, WithLayout},
+        stats::Stats,
+        totals::Totals,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
@@ -72,6 +79,32 @@ impl Leaderboard {
         &self.days
     }
 
+    /// Returns the rows of individual days,
+    /// ordered by the `key` of part `p` if `sort` is set
+    /// (see [`Row::cmp_by`]), or in their original order otherwise.
+    pub fn sorted_days(&self, sort: Option<(Part, SortKey)>) -> Vec<&Row<Day>> {
+        let mut days: Vec<_> = self.days().iter().collect();
+        if let Some((p, key)) = sort {
+            days.sort_by(|a, b| a.cmp_by(b, p, key));
+        }
+        days
+    }
+
+    /// Returns the row of day `d`, if the leaderboard contains that day.
+    #[allow(dead_code)]
+    pub fn row_for_day(&self, d: Day) -> Option<&Row<Day>> {
+        self.days
+            .iter()
+            .find(|row| row.label == d)
+    }
+
+    /// Returns the statistics of part `p` of day `d`,
+    /// if the leaderboard contains that day and that part was solved.
+    #[allow(dead_code)]
+    pub fn stats(&self, d: Day, p: Part) -> Option<&Stats> {
+        self.row_for_day(d)?.part(p)
+    }
+
     pub fn totals(&self) -> Option<&Totals> {
         self.totals.as_ref()
     }
@@ -79,6 +112,49 @@ impl Leaderboard {
     pub fn widths(&self) -> &Widths {
         &self.widths
     }
+
+    /// Returns a value that implements [`std::fmt::Display`]
+    /// and that will print this leaderboard according to `layout`.
+    pub fn with_layout<'a>(&'a self, layout: &'a Layout) -> WithLayout<'a> {
+        WithLayout {
+            board: self,
+            layout,
+        }
+    }
+}
+
+/// Selects which column of [`Stats`] to order [`Row`]s by.
+#[derive(Debug, Copy, Clone, PartialEq, Hash, Eq, clap::ValueEnum)]
+pub enum SortKey {
+    /// Best (i.e. lowest) rank first.
+    Rank,
+    /// Highest score first.
+    Score,
+}
+
+impl<T> Row<T> {
+    /// Returns the statistics of part `p`, if that part was solved.
+    pub fn part(&self, p: Part) -> Option<&Stats> {
+        match p {
+            Part::Part1 => self.parts[0].as_ref(),
+            Part::Part2 => self.parts[1].as_ref(),
+        }
+    }
+
+    /// Compares rows by the `key` of part `p`, ordering better rows first,
+    /// i.e. ascending by rank or descending by score.
+    /// Rows in which part `p` was not solved are ordered last.
+    pub fn cmp_by(&self, other: &Self, p: Part, key: SortKey) -> Ordering {
+        match (self.part(p), other.part(p)) {
+            (Some(a), Some(b)) => match key {
+                SortKey::Rank => a.rank.cmp(&b.rank),
+                SortKey::Score => b.score.cmp(&a.score),
+            },
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -86,7 +162,7 @@ mod tests {
     use indoc::indoc;
     use lazy_errors::Result;
 
-    use crate::ident::{year::Y21, Filter};
+    use crate::ident::{day::*, year::Y21, Filter};
 
     use super::*;
 
@@ -137,13 +213,262 @@ mod tests {
               1   00:20:32    6893      0          -     -      -
             -----------------------------------------------------
             MIN   00:20:32    6893      0          -     -      -
-            MED       >24h   97008      0          -     -      -
+            MED       >24h    6893      0          -     -      -
             MAX       >24h  187123      0          -     -      -
         "};
 
         assert_roundtrip(2021, input, expected)
     }
 
+    #[test]
+    fn read_and_print_avg_row() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              3       >24h  13830      0       >24h  10293      0
+              2   00:30:00   4263     20   01:00:00   4200      0
+              1   00:20:32   6893     42   00:24:50   5662      0
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              3       >24h  13830      0       >24h  10293      0
+              2   00:30:00   4263     20   01:00:00   4200      0
+              1   00:20:32   6893     42   00:24:50   5662      0
+            -----------------------------------------------------
+            MIN   00:20:32   4263      0   00:24:50   4200      0
+            MED   00:30:00   6893     20   01:00:00   5662      0
+            MAX       >24h  13830     42       >24h  10293      0
+            AVG   00:25:16   8329     20   00:42:25   6719      0
+        "};
+
+        let layout = Layout {
+            avg: true,
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)
+    }
+
+    #[test]
+    fn read_and_print_total_only() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2   00:30:00   4263     20   01:00:00   4200      0
+              1   00:20:32   6893     42   00:24:50   5662      0
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  -------Part 1--------   -------Part 2--------
+            Day       Time  Rank  Score       Time  Rank  Score
+            ---------------------------------------------------
+            MIN   00:20:32  4263     20   00:24:50  4200      0
+            MED   00:25:16  4263     20   00:42:25  4200      0
+            MAX   00:30:00  6893     42   01:00:00  5662      0
+        "};
+
+        let layout = Layout {
+            total_only: true,
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)
+    }
+
+    #[test]
+    fn read_and_print_sorted() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              3   00:10:00   6000      0          -      -      -
+              2   00:30:00   4263     20   01:00:00   4200      0
+              1   00:20:32   6893     42   00:24:50   5662      0
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  -------Part 1--------   -------Part 2--------
+            Day       Time  Rank  Score       Time  Rank  Score
+              2   00:30:00  4263     20   01:00:00  4200      0
+              3   00:10:00  6000      0          -     -      -
+              1   00:20:32  6893     42   00:24:50  5662      0
+            ---------------------------------------------------
+            MIN   00:10:00  4263      0   00:24:50  4200      0
+            MED   00:20:32  6000     20   00:42:25  4200      0
+            MAX   00:30:00  6893     42   01:00:00  5662      0
+        "};
+
+        let layout = Layout {
+            sort: Some((Part::Part1, SortKey::Rank)),
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)
+    }
+
+    #[test]
+    fn read_and_print_total_only_without_totals() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              1   00:20:32   6893     42   00:24:50   5662      0
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  -------Part 1--------   -------Part 2--------
+            Day       Time  Rank  Score       Time  Rank  Score
+            (Totals require statistics of at least two days)
+        "};
+
+        let layout = Layout {
+            total_only: true,
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)
+    }
+
+    #[test]
+    fn read_and_print_compact() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              3       >24h  13830      0          -      -      -
+              2   00:30:00   4263     20   01:00:00   4200      0
+              1   00:20:32   6893     42   00:24:50   5662      0
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                 ----Part 1-----  ----Part 2----
+            Day      Time   Rank      Time  Rank
+              3      >24h  13830         -     -
+              2  00:30:00   4263  01:00:00  4200
+              1  00:20:32   6893  00:24:50  5662
+            ------------------------------------
+            MIN  00:20:32   4263  00:24:50  4200
+            MED  00:30:00   6893  00:42:25  4200
+            MAX      >24h  13830  01:00:00  5662
+            AVG  00:25:16   8329  00:42:25  4931
+        "};
+
+        let layout = Layout {
+            avg: true,
+            compact: true,
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)?;
+
+        let output = render_with_layout(2021, input, &layout)?;
+        assert!(!output.contains("Score"));
+        Ok(())
+    }
+
+    #[test]
+    fn read_and_print_avg_row_when_all_times_are_forever() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2       >24h  13830      0          -      -      -
+              1       >24h   6893      0          -      -      -
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  --------Part 1--------   -------Part 2--------
+            Day       Time   Rank  Score       Time  Rank  Score
+              2       >24h  13830      0          -     -      -
+              1       >24h   6893      0          -     -      -
+            ----------------------------------------------------
+            MIN       >24h   6893      0          -     -      -
+            MED       >24h   6893      0          -     -      -
+            MAX       >24h  13830      0          -     -      -
+            AVG       >24h  10362      0          -     -      -
+        "};
+
+        let layout = Layout {
+            avg: true,
+            ..Layout::default()
+        };
+        assert_roundtrip_with_layout(2021, input, &layout, expected)
+    }
+
+    #[test]
+    fn lookup_rows_and_stats() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              5   00:45:25   6042      0          -      -      -
+              1   00:20:32   6893      0   00:24:50   5662      0
+        "};
+
+        let lines = input.lines().map(|s| Ok(s.to_owned()));
+        let board = parsing::parse_leaderboard(Y21, &Filter::default(), lines)?
+            .unwrap();
+
+        assert_eq!(board.row_for_day(D01).unwrap().label, D01);
+        assert_eq!(board.row_for_day(D05).unwrap().label, D05);
+        assert_eq!(board.row_for_day(D02), None);
+
+        let d01p1 = board.stats(D01, Part::Part1).unwrap();
+        let d01p2 = board.stats(D01, Part::Part2).unwrap();
+        let d05p1 = board.stats(D05, Part::Part1).unwrap();
+        assert_eq!(d01p1.rank, rank::Rank::new(6893)?);
+        assert_eq!(d01p2.rank, rank::Rank::new(5662)?);
+        assert_eq!(d05p1.rank, rank::Rank::new(6042)?);
+
+        assert_eq!(board.stats(D05, Part::Part2), None);
+        assert_eq!(board.stats(D02, Part::Part1), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn sort_rows() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              4   01:00:00   9000      0          -      -      -
+              3   00:10:00   1000     12   00:20:00   2000      0
+              2   00:45:25   6042      5   00:50:00   4000      7
+              1   00:20:32   6893      3   00:24:50   5662      4
+        "};
+
+        let lines = input.lines().map(|s| Ok(s.to_owned()));
+        let board = parsing::parse_leaderboard(Y21, &Filter::default(), lines)?
+            .unwrap();
+
+        let sorted = |p, key| -> Vec<Day> {
+            board
+                .sorted_days(Some((p, key)))
+                .into_iter()
+                .map(|row| row.label)
+                .collect()
+        };
+
+        assert_eq!(sorted(Part::Part1, SortKey::Rank), vec![
+            D03, D02, D01, D04
+        ]);
+        assert_eq!(sorted(Part::Part2, SortKey::Rank), vec![
+            D03, D02, D01, D04
+        ]);
+        assert_eq!(sorted(Part::Part1, SortKey::Score), vec![
+            D03, D02, D01, D04
+        ]);
+        assert_eq!(sorted(Part::Part2, SortKey::Score), vec![
+            D02, D01, D03, D04
+        ]);
+
+        Ok(())
+    }
+
     #[test]
     fn parse_leaderboard_fails_when_header1_is_missing() -> Result<()> {
         let input = indoc! {"\
@@ -175,6 +500,75 @@ mod tests {
         assert_err(input, "row label '0'")
     }
 
+    #[test]
+    fn read_and_print_ignores_footer() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2   03:39:44  34128      0   03:50:44  32547      0
+              1   00:20:32   6893      0   00:24:50   5662      0
+
+            To play, please identify yourself via one of these services:
+            [GitHub] [Google] [Twitter] [Reddit]
+        "};
+
+        let expected = indoc! {"\
+            Advent of Code 2021 - Personal Leaderboard Statistics
+
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2   03:39:44  34128      0   03:50:44  32547      0
+              1   00:20:32   6893      0   00:24:50   5662      0
+            -----------------------------------------------------
+            MIN   00:20:32   6893      0   00:24:50   5662      0
+            MED   02:00:08   6893      0   02:07:47   5662      0
+            MAX   03:39:44  34128      0   03:50:44  32547      0
+        "};
+
+        assert_roundtrip(2021, input, expected)
+    }
+
+    #[test]
+    fn parse_leaderboard_fails_when_row_before_footer_is_invalid() -> Result<()>
+    {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              1   00:20:32   6893      0   00:24:50   5662      0
+              2   03:39:44  34128      0   03:50:44
+            Footer
+        "};
+
+        assert_err(input, "tokenize")
+    }
+
+    #[test]
+    fn parse_leaderboard_fails_on_garbage_between_rows() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2   03:39:44  34128      0   03:50:44  32547      0
+            Garbage
+              1   00:20:32   6893      0   00:24:50   5662      0
+        "};
+
+        assert_err(input, "between table rows: 'Garbage'")
+    }
+
+    #[test]
+    fn parse_leaderboard_fails_on_blank_line_between_rows() -> Result<()> {
+        let input = indoc! {"\
+                  --------Part 1--------   --------Part 2--------
+            Day       Time   Rank  Score       Time   Rank  Score
+              2   03:39:44  34128      0   03:50:44  32547      0
+
+              1   00:20:32   6893      0   00:24:50   5662      0
+            Footer
+        "};
+
+        assert_err(input, "between table rows: ''")
+    }
+
     fn assert_roundtrip<'a>(
         year: u16,
         input: &str,
@@ -196,6 +590,31 @@ mod tests {
         Ok(())
     }
 
+    fn assert_roundtrip_with_layout(
+        year: u16,
+        input: &str,
+        layout: &Layout,
+        expected_output: &str,
+    ) -> Result<()> {
+        let actual_output = render_with_layout(year, input, layout)?;
+        assert_eq!(expected_output, actual_output);
+
+        Ok(())
+    }
+
+    fn render_with_layout(
+        year: u16,
+        input: &str,
+        layout: &Layout,
+    ) -> Result<String> {
+        let year = Year::try_from(year)?;
+        let filter = Filter::default();
+        let lines = input.lines().map(|s| Ok(s.to_owned()));
+        let board = parsing::parse_leaderboard(year, &filter, lines)?.unwrap();
+
+        Ok(board.with_layout(layout).to_string())
+    }
+
     fn assert_err(input: &str, desc: &str) -> Result<()> {
         let year = Y21;
         let filter = Filter::default();
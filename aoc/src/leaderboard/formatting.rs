@@ -1,8 +1,8 @@
 use std::fmt::Display;
 
 use crate::{
-    ident::Day,
-    leaderboard::{HeaderRow1, HeaderRow2, Leaderboard, Row, Stats},
+    ident::{Day, Part},
+    leaderboard::{HeaderRow1, HeaderRow2, Leaderboard, Row, SortKey, Stats},
 };
 
 const W_LABEL: usize = "Day".len();
@@ -10,6 +10,37 @@ const W_TIME: usize = "00:00:00".len();
 const W_RANK_MIN: usize = "Rank".len();
 const W_SCORE_MIN: usize = "Score".len();
 
+const NO_TOTALS_NOTE: &str = "(Totals require statistics of at least two days)";
+
+/// Options that control which parts of a [`Leaderboard`] will be printed.
+///
+/// The [`Default`] layout is used by [`Leaderboard`]'s [`Display`] impl.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash, Eq)]
+pub struct Layout {
+    /// Whether to print the `AVG` row after the `MIN`/`MED`/`MAX` rows.
+    pub avg: bool,
+
+    /// Whether to omit the `Score` columns and to narrow the spacing.
+    ///
+    /// Note that compact tables cannot be parsed back into a [`Leaderboard`].
+    pub compact: bool,
+
+    /// Whether to omit the rows of individual days,
+    /// printing only the `MIN`/`MED`/`MAX` (and maybe `AVG`) rows.
+    pub total_only: bool,
+
+    /// The part and column to order the rows of individual days by,
+    /// or `None` to print them in their original order.
+    pub sort: Option<(Part, SortKey)>,
+}
+
+/// A [`Leaderboard`] that will be printed according to a [`Layout`].
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+pub struct WithLayout<'a> {
+    pub board:  &'a Leaderboard,
+    pub layout: &'a Layout,
+}
+
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
 pub struct Adjusted<'a, T: ?Sized> {
     pub element: &'a T,
@@ -43,6 +74,23 @@ impl<'a, T: 'a> Formattable for T where Adjusted<'a, T>: Display {}
 
 impl Display for Leaderboard {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.fmt_with_layout(f, &Layout::default())
+    }
+}
+
+impl Display for WithLayout<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.board
+            .fmt_with_layout(f, self.layout)
+    }
+}
+
+impl Leaderboard {
+    fn fmt_with_layout(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        layout: &Layout,
+    ) -> std::fmt::Result {
         let year = self.year();
         let header1 = HeaderRow1 {};
         let header2 = HeaderRow2 {};
@@ -50,11 +98,17 @@ impl Display for Leaderboard {
         writeln!(f, "Advent of Code {year} - Personal Leaderboard Statistics")?;
         writeln!(f)?;
 
+        if layout.compact {
+            return self.fmt_compact(f, layout);
+        }
+
         write!(f, "{}", header1.adjust_to(self.widths()))?;
         write!(f, "{}", header2.adjust_to(self.widths()))?;
 
-        for row in self.days() {
-            write!(f, "{}", row.adjust_to(self.widths()))?;
+        if !layout.total_only {
+            for row in self.sorted_days(layout.sort) {
+                write!(f, "{}", row.adjust_to(self.widths()))?;
+            }
         }
 
         if let Some(totals) = self.totals() {
@@ -63,10 +117,79 @@ impl Display for Leaderboard {
             for row in &totals.rows {
                 write!(f, "{}", row.adjust_to(self.widths()))?;
             }
+            if layout.avg {
+                write!(f, "{}", totals.avg.adjust_to(self.widths()))?;
+            }
+        } else if layout.total_only {
+            writeln!(f, "{NO_TOTALS_NOTE}")?;
         }
 
         Ok(())
     }
+
+    /// Prints the table without the `Score` columns,
+    /// separating columns by two spaces only.
+    fn fmt_compact(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        layout: &Layout,
+    ) -> std::fmt::Result {
+        let widths = self.widths();
+        let parts = widths
+            .parts
+            .each_ref()
+            .map(|w| W_TIME + 2 + w.rank);
+
+        write!(f, "   ")?;
+        write!(f, "  {:-^width$}", "Part 1", width = parts[0])?;
+        write!(f, "  {:-^width$}", "Part 2", width = parts[1])?;
+        writeln!(f)?;
+
+        let (t, r) = ("Time", "Rank");
+        let [w_r1, w_r2] = widths.parts.each_ref().map(|w| w.rank);
+        writeln!(f, "Day  {t:>8}  {r:>w_r1$}  {t:>8}  {r:>w_r2$}")?;
+
+        if !layout.total_only {
+            for row in self.sorted_days(layout.sort) {
+                fmt_compact_row(f, row, widths)?;
+            }
+        }
+
+        if let Some(totals) = self.totals() {
+            let width_total = W_LABEL + 2 + parts[0] + 2 + parts[1];
+            writeln!(f, "{:-^width_total$}", "")?;
+            for row in &totals.rows {
+                fmt_compact_row(f, row, widths)?;
+            }
+            if layout.avg {
+                fmt_compact_row(f, &totals.avg, widths)?;
+            }
+        } else if layout.total_only {
+            writeln!(f, "{NO_TOTALS_NOTE}")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn fmt_compact_row<T: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    row: &Row<T>,
+    widths: &Widths,
+) -> std::fmt::Result {
+    write!(f, "{:3}", row.label)?;
+
+    for (stats, widths) in row.parts.iter().zip(&widths.parts) {
+        let w_r = widths.rank;
+        match stats {
+            Some(Stats { time, rank, .. }) => {
+                write!(f, "  {time:>8}  {rank:>w_r$}")?;
+            }
+            None => write!(f, "  {:>8}  {:>w_r$}", '-', '-')?,
+        }
+    }
+
+    writeln!(f)
 }
 
 impl Display for Adjusted<'_, HeaderRow1> {
@@ -1,19 +1,43 @@
+use std::time::Duration;
+
 use itertools::Either;
 use num::Integer;
 
+use crate::leaderboard::time::Time;
+
 pub trait Mean {
     fn mean(&self, right: &Self) -> Self;
 }
 
+/// Picks the median of an even number of values,
+/// given the two values in the middle.
+///
+/// Types that represent quantities (like times) can simply
+/// delegate to [`Mean`]. Ordinal types (like ranks or scores)
+/// should pick one of the values instead of averaging them,
+/// since the mean may be a value that nobody actually had.
+pub trait MedianOfTwo {
+    fn median_of_two(left: &Self, right: &Self) -> Self;
+}
+
+/// Computes the arithmetic mean of an arbitrary number of values.
+///
+/// In contrast to [`Mean`], which only considers two values,
+/// this trait considers all values at once.
+pub trait Average: Sized {
+    /// Returns the arithmetic mean of `values`, or `None` if it's empty.
+    fn average(values: &[Self]) -> Option<Self>;
+}
+
 pub trait Median<T>
 where
     Self: AsRef<[T]>,
-    T: Mean + Copy,
+    T: MedianOfTwo + Copy,
 {
     fn median(&self) -> Option<T> {
         match middle(&self.as_ref())? {
             Either::Left(middle) => Some(*middle),
-            Either::Right((left, right)) => Some(T::mean(left, right)),
+            Either::Right((left, right)) => Some(T::median_of_two(left, right)),
         }
     }
 }
@@ -21,13 +45,13 @@ where
 impl<T, U> Median<T> for U
 where
     U: AsRef<[T]> + ?Sized,
-    T: Mean + Copy,
+    T: MedianOfTwo + Copy,
 {
 }
 
 pub fn min_med_max_sorted<T, U>(slice: &U) -> Option<(T, T, T)>
 where
-    T: Mean + Copy,
+    T: MedianOfTwo + Copy,
     U: AsRef<[T]> + Median<T> + ?Sized,
 {
     let slice = slice.as_ref();
@@ -38,6 +62,37 @@ where
     Some((min, med, max))
 }
 
+/// Computes the `p`-th percentile of `sorted`, where `p` is in `0..=100`.
+///
+/// If the percentile falls between two values, it is interpolated linearly
+/// and rounded to whole seconds, so the 50th percentile is the same as
+/// the median. [`Time::Forever`] values are sorted last. Percentiles that
+/// fall on or next to such a value are [`Time::Forever`] as well,
+/// since there is no way of knowing how long they actually took.
+///
+/// Returns `None` if `sorted` is empty or if `p` is out of range.
+#[allow(dead_code)]
+pub fn percentile(sorted: &[Time], p: f64) -> Option<Time> {
+    if sorted.is_empty() || !(0.0..=100.0).contains(&p) {
+        return None;
+    }
+
+    let pos = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = pos.floor() as usize;
+    let hi = pos.ceil() as usize;
+
+    match (sorted[lo], sorted[hi]) {
+        (t, _) if lo == hi => Some(t),
+        (Time::Exactly(l), Time::Exactly(r)) => {
+            let l = l.as_secs_f64();
+            let r = r.as_secs_f64();
+            let secs = l + (r - l) * (pos - lo as f64);
+            Some(Time::Exactly(Duration::from_secs(secs.round() as u64)))
+        }
+        _ => Some(Time::Forever),
+    }
+}
+
 fn middle<T, U>(container: &U) -> Option<Either<&T, (&T, &T)>>
 where
     U: AsRef<[T]>,
@@ -57,6 +112,7 @@ where
 
 #[cfg(test)]
 mod tests {
+    use lazy_errors::Result;
     use test_case::test_case;
 
     use super::*;
@@ -71,6 +127,12 @@ mod tests {
         }
     }
 
+    impl MedianOfTwo for TestData {
+        fn median_of_two(left: &Self, right: &Self) -> Self {
+            left.mean(right)
+        }
+    }
+
     #[test_case(&[], None; "Empty")]
     #[test_case(&[42], Some(Either::Left(&42)); "Single element")]
     #[test_case(&[0, 42], Some(Either::Right((&0, &42))); "Two elements")]
@@ -84,6 +146,56 @@ mod tests {
         assert_eq!(expectation, super::middle(&slice))
     }
 
+    #[test_case(&[], 50.0, None; "Empty slice")]
+    #[test_case(&["00:00:01"], 101.0, None; "Out of range")]
+    #[test_case(&["00:00:01"], -1.0, None; "Negative")]
+    #[test_case(&["00:00:01"], 90.0, Some("00:00:01"); "Single element")]
+    #[test_case(
+        &["00:00:10", "00:00:20", "00:00:30", "00:00:40", "00:00:50",
+          "00:01:00", "00:01:10", "00:01:20", "00:01:30", "00:01:40"],
+        90.0, Some("00:01:31"); "p90 interpolated")]
+    #[test_case(
+        &["00:00:10", "00:00:20", "00:00:30", "00:00:40", "00:00:50",
+          "00:01:00", "00:01:10", "00:01:20", "00:01:30", "00:01:40", ">24h"],
+        90.0, Some("00:01:40"); "p90 exact")]
+    #[test_case(
+        &["00:00:10", "00:00:20", "00:00:30", ">24h"],
+        90.0, Some(">24h"); "p90 next to forever")]
+    fn percentile(
+        sorted: &[&str],
+        p: f64,
+        expected: Option<&str>,
+    ) -> Result<()> {
+        let sorted = sorted
+            .iter()
+            .map(|&t| Time::try_from(t))
+            .collect::<Result<Vec<_>>>()?;
+        let expected = expected
+            .map(Time::try_from)
+            .transpose()?;
+
+        assert_eq!(super::percentile(&sorted, p), expected);
+        Ok(())
+    }
+
+    #[test_case(&["00:00:01", "00:01:00", "01:00:00"]; "Odd")]
+    #[test_case(&["00:00:01", "00:00:02", "00:01:00", "01:00:00"]; "Even")]
+    #[test_case(&["00:00:00", "00:00:01"]; "Even rounded up")]
+    #[test_case(&["00:00:00", "01:00:00", ">24h"]; "Forever in the middle")]
+    #[test_case(&["00:00:00", "01:00:00", ">24h", ">24h"]; "Forever")]
+    fn percentile_matches_min_med_max(sorted: &[&str]) -> Result<()> {
+        let sorted = sorted
+            .iter()
+            .map(|&t| Time::try_from(t))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (min, med, max) = super::min_med_max_sorted(&sorted).unwrap();
+        assert_eq!(super::percentile(&sorted, 0.0), Some(min));
+        assert_eq!(super::percentile(&sorted, 50.0), Some(med));
+        assert_eq!(super::percentile(&sorted, 100.0), Some(max));
+        Ok(())
+    }
+
     #[test_case(&[], None; "Empty slice")]
     #[test_case(
         &[0, 42, 1337], Some((0, 42, 1337));
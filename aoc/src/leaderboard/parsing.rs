@@ -1,8 +1,9 @@
-use std::{fs::DirEntry, str::FromStr};
+use std::{collections::HashMap, fs::DirEntry, str::FromStr, time::Duration};
 
 use itertools::Itertools;
 use lazy_errors::{prelude::*, Result};
 use lazy_regex::regex_is_match;
+use serde::Deserialize;
 
 use crate::{
     fs::{self, Config},
@@ -10,10 +11,42 @@ use crate::{
     parser,
 };
 
-use super::{stats::Stats, HeaderRow1, HeaderRow2, Leaderboard, Row};
+use super::{
+    rank::Rank, score::Score, stats::Stats, time::Time, HeaderRow1, HeaderRow2,
+    Leaderboard, Row,
+};
 
-#[cfg(test)]
-use super::{rank::Rank, score::Score, time::Time};
+/// The format of a file in the personal leaderboard directory.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+enum FileFormat {
+    /// Personal leaderboard statistics copied from adventofcode.com.
+    Text,
+    /// The JSON of a private leaderboard from adventofcode.com.
+    Json,
+}
+
+/// The parts of the private leaderboard JSON we are interested in.
+#[derive(Debug, Deserialize)]
+struct PrivateLeaderboard {
+    owner_id: u64,
+    members:  HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+
+    /// Maps the day (`"1"` to `"25"`) to the part (`"1"` or `"2"`)
+    /// to the star the member got for that part.
+    #[serde(default)]
+    completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    /// Unix timestamp of when the member solved the part.
+    get_star_ts: u64,
+}
 
 impl FromStr for HeaderRow1 {
     type Err = Error;
@@ -75,11 +108,68 @@ pub fn parse_leaderboard(
     let _: HeaderRow1 = parser::parse_next_ok(&mut lines).or_wrap_with(msg)?;
     let _: HeaderRow2 = parser::parse_next_ok(&mut lines).or_wrap_with(msg)?;
 
-    let days: Vec<Row<Day>> = parser::parse_each_ok(lines)
+    // Users may have copied more than just the table, such as a footer.
+    // Stop at the first line that is not a table row, but fail on lines
+    // that look like table rows yet cannot be parsed.
+    let mut lines = lines.peekable();
+    let rows = lines.peeking_take_while(|line| match line {
+        Ok(line) => looks_like_row(line),
+        Err(_) => true,
+    });
+
+    let days: Vec<Row<Day>> = parser::parse_each_ok(rows)
         .filter_ok(|row: &Row<Day>| filter.matches_year_day(year, row.label))
         .try_collect()
         .or_wrap_with(msg)?;
 
+    ensure_no_rows_after_table(lines).or_wrap_with(msg)?;
+
+    Ok(Leaderboard::new(year, days))
+}
+
+/// Fails if any of the `lines` following the table looks like a table row,
+/// because that row would be dropped silently otherwise.
+fn ensure_no_rows_after_table(
+    mut lines: impl Iterator<Item = Result<String>>,
+) -> Result<()> {
+    let Some(end) = lines.next().transpose()? else {
+        return Ok(());
+    };
+
+    for line in lines {
+        if looks_like_row(&line?) {
+            return Err(err!("Unexpected line between table rows: '{end}'"));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses the JSON adventofcode.com serves for a private leaderboard
+/// into the statistics of the leaderboard's owner.
+///
+/// Since the JSON only contains the members of the private leaderboard,
+/// [`Rank`] is the owner's rank among those members and
+/// [`Score`] is the number of points the owner got on the private leaderboard.
+/// Like on the personal leaderboard statistics page, times of 24 hours or more
+/// are treated as [`Time::Forever`].
+pub fn parse_leaderboard_json(
+    year: Year,
+    filter: &Filter,
+    json: &str,
+) -> Result<Option<Leaderboard>> {
+    let msg = || format!("Failed to parse {year} leaderboard");
+
+    let board: PrivateLeaderboard = serde_json::from_str(json)
+        .or_wrap_with::<Stashable>(|| "Invalid JSON")
+        .or_wrap_with(msg)?;
+
+    let days = parse_days_of_owner(year, &board)
+        .or_wrap_with(msg)?
+        .into_iter()
+        .filter(|row| filter.matches_year_day(year, row.label))
+        .collect();
+
     Ok(Leaderboard::new(year, days))
 }
 
@@ -89,12 +179,14 @@ pub fn parse_leaderboards_from_fs(
 ) -> Result<Vec<Leaderboard>> {
     parse_years_from_fs(config)?
         .into_iter()
-        .filter(|&y| filter.matches_year(y))
-        .flat_map(|y| parse_leaderboard_from_fs(y, config, filter).transpose())
+        .filter(|&(y, _)| filter.matches_year(y))
+        .flat_map(|(y, format)| {
+            parse_leaderboard_from_fs(y, format, config, filter).transpose()
+        })
         .try_collect()
 }
 
-fn parse_years_from_fs(config: &Config) -> Result<Vec<Year>> {
+fn parse_years_from_fs(config: &Config) -> Result<Vec<(Year, FileFormat)>> {
     let dir = config.personal_leaderboard_dir();
 
     let mut errs = ErrorStash::new(|| {
@@ -106,23 +198,31 @@ fn parse_years_from_fs(config: &Config) -> Result<Vec<Year>> {
         .or_wrap_with::<Stashable>(|| "Failed to read directory")
         .or_stash(&mut errs));
 
-    let mut years: Vec<Year> = try2!(entries
+    let mut years: Vec<(Year, FileFormat)> = try2!(entries
         .iter()
         .map(|e| {
             let name = e.file_name();
             let name = name.to_string_lossy();
 
             lazy_regex::regex_captures!(
-                r"^(y\d{2})_personal_leaderboard_statistics.txt$",
+                r"^(y\d{2})_personal_leaderboard_statistics\.(txt|json)$",
                 &name
             )
             .ok_or_else(|| {
                 err!(
                     "File name does not match pattern \
-                     'yYY_personal_leaderboard_statistics.txt'"
+                     'yYY_personal_leaderboard_statistics.txt' or \
+                     'yYY_personal_leaderboard_statistics.json'"
                 )
             })
-            .and_then(|(_, y)| y.parse().map(|Id::<Year>(y)| y))
+            .and_then(|(_, y, ext)| {
+                let Id::<Year>(y) = y.parse()?;
+                let format = match ext {
+                    "json" => FileFormat::Json,
+                    _ => FileFormat::Text,
+                };
+                Ok((y, format))
+            })
             .or_wrap_with::<Stashable>(|| {
                 format!("Failed to parse file name '{name}'")
             })
@@ -131,16 +231,39 @@ fn parse_years_from_fs(config: &Config) -> Result<Vec<Year>> {
 
     years.sort_unstable();
 
+    if let Some(((y, _), _)) = years
+        .iter()
+        .tuple_windows()
+        .find(|((a, _), (b, _))| a == b)
+    {
+        return Err(err!(
+            "Found more than one {y} leaderboard in '{}'",
+            dir.display()
+        ));
+    }
+
     Ok(years)
 }
 
 fn parse_leaderboard_from_fs(
     year: Year,
+    format: FileFormat,
     config: &Config,
     filter: &Filter,
 ) -> Result<Option<Leaderboard>> {
-    let lines = read_leaderboard_lines(year, config)?;
-    parse_leaderboard(year, filter, lines)
+    match format {
+        FileFormat::Text => {
+            let lines = read_leaderboard_lines(year, config)?;
+            parse_leaderboard(year, filter, lines)
+        }
+        FileFormat::Json => {
+            let path = config.personal_leaderboard_json_file(year);
+            let json = fs::read_to_string(path).or_wrap_with(|| {
+                format!("Failed to read {year} leaderboard")
+            })?;
+            parse_leaderboard_json(year, filter, &json)
+        }
+    }
 }
 
 fn read_leaderboard_lines(
@@ -153,6 +276,111 @@ fn read_leaderboard_lines(
         .map(fs::lines)
 }
 
+/// Returns `true` if `line` starts with a number, like a row label would.
+fn looks_like_row(line: &str) -> bool {
+    regex_is_match!(r"^\s*\d+(\s|$)", line)
+}
+
+/// Returns one row per day on which the owner of `board` got a star,
+/// starting with the most recent day like the personal leaderboard does.
+fn parse_days_of_owner(
+    year: Year,
+    board: &PrivateLeaderboard,
+) -> Result<Vec<Row<Day>>> {
+    let owner = board
+        .members
+        .values()
+        .find(|m| m.id == board.owner_id)
+        .ok_or_else(|| {
+            err!(
+                "Owner {} is not a member of the leaderboard",
+                board.owner_id
+            )
+        })?;
+
+    let mut days: Vec<Row<Day>> = owner
+        .completion_day_level
+        .iter()
+        .map(|(day, parts)| {
+            let label: Day = day.parse()?;
+            let parts = [
+                parse_star(year, label, "1", parts, board)?,
+                parse_star(year, label, "2", parts, board)?,
+            ];
+            Ok::<_, Error>(Row { label, parts })
+        })
+        .try_collect()?;
+
+    days.sort_unstable_by_key(|row| std::cmp::Reverse(row.label));
+    Ok(days)
+}
+
+fn parse_star(
+    year: Year,
+    day: Day,
+    part: &str,
+    parts: &HashMap<String, Star>,
+    board: &PrivateLeaderboard,
+) -> Result<Option<Stats>> {
+    let Some(star) = parts.get(part) else {
+        return Ok(None);
+    };
+
+    let ts = star.get_star_ts;
+    let released = release_timestamp(year, day);
+    let time = ts
+        .checked_sub(released)
+        .ok_or_else(|| {
+            err!("Star for day {day} part {part} predates the puzzle")
+        })?;
+    let time = match time {
+        t if t < 24 * 60 * 60 => Time::Exactly(Duration::from_secs(t)),
+        _ => Time::Forever,
+    };
+
+    let faster = board
+        .members
+        .values()
+        .filter_map(|m| {
+            m.completion_day_level
+                .get(&day.to_string())
+        })
+        .filter_map(|parts| parts.get(part))
+        .filter(|other| other.get_star_ts < ts)
+        .count();
+
+    let rank = u32::try_from(faster + 1)
+        .or_wrap_with(|| "Too many leaderboard members")?;
+
+    // On private leaderboards, the first member to get a star
+    // gets one point per member, the second one point less, and so on.
+    let points = board.members.len() - faster;
+    let score = u16::try_from(points)
+        .or_wrap_with(|| "Too many leaderboard members")?;
+
+    Ok(Some(Stats::new(time, Rank::new(rank)?, Score::new(score))))
+}
+
+/// Returns the Unix timestamp of midnight EST (UTC-5) on the given day,
+/// which is when adventofcode.com releases the puzzle.
+fn release_timestamp(year: Year, day: Day) -> u64 {
+    const DAY: u64 = 24 * 60 * 60;
+
+    let year = u64::from(u16::from(year));
+    let years_since_epoch = year - 1970;
+
+    // Leap years from 1970 up to and including `year`,
+    // which is correct until the year 2100.
+    let leap_days = year / 4 - 1969 / 4;
+
+    // From the Unix epoch until December 1st of `year`.
+    let days_until_december = 334 + years_since_epoch * 365 + leap_days;
+    let days_until_day = u64::from(u8::from(day)) - 1;
+
+    let days = days_until_december + days_until_day;
+    days * DAY + 5 * 60 * 60
+}
+
 fn parse_part_cols(
     time: &str,
     rank: &str,
@@ -277,8 +505,13 @@ mod tests {
             .to_string_lossy()
             .into_owned();
 
-        let y = Year::try_from(2023)?;
-        let result = parse_leaderboard_from_fs(y, &config, &Filter::default());
+        let y = Year::try_from(2023u16)?;
+        let result = parse_leaderboard_from_fs(
+            y,
+            FileFormat::Text,
+            &config,
+            &Filter::default(),
+        );
         let msg = result.unwrap_err().to_string();
 
         dbg!(&msg, &path);
@@ -288,6 +521,150 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_in`
+    fn parse_from_fs_when_dir_contains_json() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+
+        let mut path = tempdir.path().to_path_buf();
+        path.push("personal_leaderboard_statistics");
+        std::fs::create_dir(&path).unwrap();
+
+        path.push("y21_personal_leaderboard_statistics.json");
+        std::fs::write(&path, PRIVATE_LEADERBOARD_JSON).unwrap();
+
+        let config = fs::create_config_for(&tempdir)?;
+        let boards = parse_leaderboards_from_fs(&config, &Filter::default())?;
+
+        assert_eq!(boards.len(), 1);
+        assert_eq!(boards[0].year(), Year::try_from(2021u16)?);
+        assert_eq!(boards[0].days().len(), 2);
+
+        path.set_extension("txt");
+        std::fs::write(&path, "").unwrap();
+
+        let result = parse_leaderboards_from_fs(&config, &Filter::default());
+        let msg = result.unwrap_err().to_string();
+
+        dbg!(&msg);
+        assert!(msg.contains("Found more than one 2021 leaderboard"));
+
+        Ok(())
+    }
+
+    /// The owner (`1`) got the first star of day 1 after member `2`
+    /// and the second star before them.
+    /// Member `3` did not get any star.
+    const PRIVATE_LEADERBOARD_JSON: &str = r#"{
+        "event": "2021",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1,
+                "name": "Owner",
+                "stars": 3,
+                "local_score": 8,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1638336034, "star_index": 1 },
+                        "2": { "get_star_ts": 1638336800, "star_index": 2 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1638511200, "star_index": 3 }
+                    }
+                }
+            },
+            "2": {
+                "id": 2,
+                "name": null,
+                "stars": 2,
+                "local_score": 5,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1638334900, "star_index": 4 },
+                        "2": { "get_star_ts": 1638337800, "star_index": 5 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3,
+                "name": "Lurker",
+                "stars": 0,
+                "local_score": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parse_leaderboard_json() -> Result<()> {
+        let y = Year::try_from(2021u16)?;
+        let board = super::parse_leaderboard_json(
+            y,
+            &Filter::default(),
+            PRIVATE_LEADERBOARD_JSON,
+        )?
+        .unwrap();
+
+        let stats = |secs, rank, score| -> Result<Option<Stats>> {
+            let time = match secs {
+                Some(secs) => Time::Exactly(Duration::from_secs(secs)),
+                None => Time::Forever,
+            };
+            Ok(Some(Stats::new(time, Rank::new(rank)?, Score::new(score))))
+        };
+
+        assert_eq!(board.days(), [
+            Row {
+                label: Day::try_from(2u8)?,
+                parts: [stats(None, 1, 3)?, None],
+            },
+            Row {
+                label: Day::try_from(1u8)?,
+                parts: [stats(Some(1234), 2, 2)?, stats(Some(2000), 1, 3)?],
+            },
+        ]);
+
+        Ok(())
+    }
+
+    #[test_case(
+        r#"{ "owner_id": 4, "members": {} }"#,
+        "Owner 4 is not a member";
+        "Owner missing")]
+    #[test_case(
+        r#"{ "owner_id": 1, "members": { "1": { "id": 1,
+            "completion_day_level": { "1": { "1": { "get_star_ts": 42 } } }
+        } } }"#,
+        "predates the puzzle";
+        "Star before release")]
+    #[test_case(
+        r#"{ "owner_id": 1, "members": { "1": { "id": 1,
+            "completion_day_level": { "26": {} }
+        } } }"#,
+        "Day 26 is out of range";
+        "Day out of range")]
+    #[test_case("[Return to Event]", "Invalid JSON"; "Not JSON")]
+    fn parse_leaderboard_json_fails_gracefully(json: &str, expected: &str) {
+        let y = Year::try_from(2021u16).unwrap();
+        let err = super::parse_leaderboard_json(y, &Filter::default(), json)
+            .unwrap_err();
+
+        let msg = format!("{err:#}");
+        dbg!(&msg);
+        assert!(msg.contains("Failed to parse 2021 leaderboard"));
+        assert!(msg.contains(expected));
+    }
+
+    #[test_case(2021, 1, 1638334800)]
+    #[test_case(2024, 25, 1735102800)]
+    fn release_timestamp(year: u16, day: u8, expected: u64) -> Result<()> {
+        let year = Year::try_from(year)?;
+        let day = Day::try_from(day)?;
+        assert_eq!(super::release_timestamp(year, day), expected);
+        Ok(())
+    }
+
     #[test_case("", false, false; "Empty string does not belong to table head")]
     #[test_case(
         "      --------Part 1--------   --------Part 2--------", true, false;
@@ -303,6 +680,20 @@ mod tests {
         assert_parsing::<HeaderRow2>(line, is_line_2);
     }
 
+    #[test_case(
+        "  1   00:20:32   6893      0   00:24:50   5662      0", true;
+        "Valid row")]
+    #[test_case(
+        "  0   00:00:00      0      0   00:00:00      0      0", true;
+        "Invalid row that looks like a row")]
+    #[test_case(" 25", true; "Incomplete row")]
+    #[test_case("", false; "Empty line")]
+    #[test_case("   ", false; "Blank line")]
+    #[test_case("[Return to Event]", false; "Footer")]
+    fn looks_like_row(line: &str, expected: bool) {
+        assert_eq!(super::looks_like_row(line), expected);
+    }
+
     #[test]
     fn parse_table_row() -> Result<()> {
         let Row {
@@ -315,7 +706,7 @@ mod tests {
         let part1 = part1.unwrap();
         let part2 = part2.unwrap();
 
-        assert_eq!(day, Day::try_from(9)?);
+        assert_eq!(day, Day::try_from(9u8)?);
 
         assert_eq!(part1.time, Time::Exactly(Duration::new(45 + 44 * 60, 0)));
         assert_eq!(part1.rank, Rank::new(8618)?);
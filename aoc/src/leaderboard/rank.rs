@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use lazy_errors::{prelude::*, Result};
 use num::integer::average_ceil;
 
-use crate::leaderboard::min_med_max::Mean;
+use crate::leaderboard::min_med_max::{Average, Mean, MedianOfTwo};
 
 #[derive(
     Debug,
@@ -47,6 +47,33 @@ impl Mean for Rank {
     }
 }
 
+impl MedianOfTwo for Rank {
+    /// Picks the lower-middle value instead of averaging.
+    fn median_of_two(left: &Self, _right: &Self) -> Self {
+        *left
+    }
+}
+
+impl Average for Rank {
+    /// Computes the mean rank, rounded up (i.e. to the worse rank).
+    fn average(values: &[Self]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let sum: u64 = values
+            .iter()
+            .map(|r| u64::from(r.0))
+            .sum();
+        let len = u64::try_from(values.len()).ok()?;
+        let avg = sum.div_ceil(len);
+
+        // "Cannot" fail
+        let avg = u32::try_from(avg).expect("Average of u32 to fit in u32");
+        Some(Rank::new(avg).expect("Average of valid Ranks to be valid"))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
@@ -92,6 +119,21 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(&[], None; "Empty")]
+    #[test_case(&[42], Some(42); "Single element")]
+    #[test_case(&[1, 2], Some(2); "Chooses the worse rank if in-between")]
+    #[test_case(&[1, 2, 3, 10], Some(4); "Multiple elements")]
+    fn average_of_slice(slice: &[u32], exp: Option<u32>) -> Result<()> {
+        let ranks = slice
+            .iter()
+            .map(|&r| Rank::new(r))
+            .collect::<Result<Vec<_>>>()?;
+
+        let exp = exp.map(Rank::new).transpose()?;
+        assert_eq!(Rank::average(&ranks), exp);
+        Ok(())
+    }
+
     #[test]
     fn min_med_max() -> Result<()> {
         let mut vec = vec![
@@ -108,4 +150,18 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test_case(&[1, 2], 1; "Two elements")]
+    #[test_case(&[7, 30, 42, 100], 30; "Four elements")]
+    #[test_case(&[5, 5, 9, 9], 5; "Duplicates")]
+    fn min_med_max_even(slice: &[u32], med: u32) -> Result<()> {
+        let ranks = slice
+            .iter()
+            .map(|&r| Rank::new(r))
+            .collect::<Result<Vec<_>>>()?;
+
+        let (_, actual, _) = min_med_max_sorted(&ranks).unwrap();
+        assert_eq!(actual, Rank::new(med)?);
+        Ok(())
+    }
 }
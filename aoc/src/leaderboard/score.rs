@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use lazy_errors::{prelude::*, Result};
 use num::integer::average_floor;
 
-use crate::leaderboard::min_med_max::Mean;
+use crate::leaderboard::min_med_max::{Average, Mean, MedianOfTwo};
 
 #[derive(
     Debug,
@@ -42,6 +42,33 @@ impl Mean for Score {
     }
 }
 
+impl MedianOfTwo for Score {
+    /// Picks the lower-middle value instead of averaging.
+    fn median_of_two(left: &Self, _right: &Self) -> Self {
+        *left
+    }
+}
+
+impl Average for Score {
+    /// Computes the mean score, rounded down (i.e. to the worse score).
+    fn average(values: &[Self]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let sum: u64 = values
+            .iter()
+            .map(|s| u64::from(s.0))
+            .sum();
+        let len = u64::try_from(values.len()).ok()?;
+        let avg = sum / len;
+
+        // "Cannot" fail
+        let avg = u16::try_from(avg).expect("Average of u16 to fit in u16");
+        Some(Score::new(avg))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
@@ -82,6 +109,19 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(&[], None; "Empty")]
+    #[test_case(&[42], Some(42); "Single element")]
+    #[test_case(&[1, 2], Some(1); "Chooses the worse score if in-between")]
+    #[test_case(&[0, 10, 20, 100], Some(32); "Multiple elements")]
+    fn average_of_slice(slice: &[u16], exp: Option<u16>) {
+        let scores: Vec<Score> = slice
+            .iter()
+            .map(|&s| Score::new(s))
+            .collect();
+
+        assert_eq!(Score::average(&scores), exp.map(Score::new));
+    }
+
     #[test]
     fn min_med_max() -> Result<()> {
         let mut vec = vec![
@@ -98,4 +138,17 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test_case(&[1, 2], 1; "Two elements")]
+    #[test_case(&[7, 30, 42, 100], 30; "Four elements")]
+    #[test_case(&[0, 0, 20, 20], 0; "Duplicates")]
+    fn min_med_max_even(slice: &[u16], med: u16) {
+        let scores: Vec<Score> = slice
+            .iter()
+            .map(|&s| Score::new(s))
+            .collect();
+
+        let (_, actual, _) = min_med_max_sorted(&scores).unwrap();
+        assert_eq!(actual, Score::new(med));
+    }
 }
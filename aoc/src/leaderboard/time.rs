@@ -8,7 +8,11 @@ use itertools::Itertools;
 use lazy_errors::{prelude::*, Result};
 use num::integer::{average_ceil, div_rem};
 
-use crate::leaderboard::min_med_max::Mean;
+use crate::leaderboard::min_med_max::{Average, Mean, MedianOfTwo};
+
+/// How adventofcode.com displays times of at least a day,
+/// i.e. [`Time::Forever`].
+pub const FOREVER: &str = ">24h";
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash, Eq)]
 pub enum Time {
@@ -48,6 +52,45 @@ impl Mean for Time {
     }
 }
 
+impl MedianOfTwo for Time {
+    fn median_of_two(left: &Self, right: &Self) -> Self {
+        left.mean(right)
+    }
+}
+
+impl Average for Time {
+    /// Computes the mean of all [`Time::Exactly`] values,
+    /// rounded up to whole seconds (just like [`Mean`]).
+    ///
+    /// [`Time::Forever`] values are _excluded_ from the mean,
+    /// since there is no way of knowing how long they actually took.
+    /// Thus, the result may be much lower than the actual mean time.
+    /// If `values` contains only [`Time::Forever`] values,
+    /// the result will be [`Time::Forever`].
+    fn average(values: &[Self]) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        let secs: Vec<u64> = values
+            .iter()
+            .filter_map(|t| match t {
+                Time::Exactly(t) => Some(t.as_secs()),
+                Time::Forever => None,
+            })
+            .collect();
+
+        if secs.is_empty() {
+            return Some(Time::Forever);
+        }
+
+        let sum: u64 = secs.iter().sum();
+        let len = u64::try_from(secs.len()).ok()?;
+        let mean_secs = sum.div_ceil(len);
+        Some(Time::Exactly(Duration::from_secs(mean_secs)))
+    }
+}
+
 impl Display for Time {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -57,7 +100,7 @@ impl Display for Time {
                 let string = format!("{h:02}:{m:02}:{s:02}");
                 Display::fmt(&string, f)
             }
-            Time::Forever => Display::fmt(&">24h", f),
+            Time::Forever => Display::fmt(&FOREVER, f),
         }
     }
 }
@@ -66,15 +109,32 @@ impl TryFrom<&str> for Time {
     type Error = Error;
 
     fn try_from(text: &str) -> Result<Self> {
+        Time::parse_with_limit(text, FOREVER)
+    }
+}
+
+impl Time {
+    /// Like [`Time::try_from`], but parses `limit` as [`Time::Forever`]
+    /// instead of `>24h`, e.g. for timing files with a different cap.
+    pub fn parse_with_limit(text: &str, limit: &str) -> Result<Self> {
+        if text == limit && !text.is_empty() {
+            return Ok(Time::Forever);
+        }
+
         let err_bad_pattern = || {
             Error::from_message(format!(
                 "Input does not match pattern hh:mm:ss: '{text}'"
             ))
         };
 
+        // Some boards may contain fractional seconds, e.g. `00:00:01.500`.
+        let (text, fraction) = match text.split_once('.') {
+            Some((hms, fraction)) => (hms, Some(fraction)),
+            None => (text, None),
+        };
+
         match text {
             "" => Err(err_bad_pattern()),
-            ">24h" => Ok(Time::Forever),
             _ => text
                 .split(':')
                 .map(|k| {
@@ -96,13 +156,57 @@ impl TryFrom<&str> for Time {
                         return Err(err!("'{s}' not in range 00..60"));
                     }
 
-                    Ok(Time::Exactly(Duration::from_secs(
-                        s + 60 * m + 60 * 60 * h,
-                    )))
+                    let secs = s + 60 * m + 60 * 60 * h;
+                    let nanos = fraction
+                        .map(parse_nanos)
+                        .transpose()?
+                        .unwrap_or(0);
+                    Ok(Time::Exactly(Duration::new(secs, nanos)))
                 }),
         }
         .or_wrap_with(|| "Invalid time")
     }
+
+    /// Like [`Display`], but formats [`Time::Forever`] as `limit`
+    /// instead of `>24h` (see [`Time::parse_with_limit`]).
+    #[allow(dead_code)]
+    pub fn to_string_with_limit(self, limit: &str) -> String {
+        match self {
+            Time::Exactly(_) => self.to_string(),
+            Time::Forever => limit.to_owned(),
+        }
+    }
+
+    /// Like [`Display`], but formats [`Time::Exactly`] values
+    /// with millisecond precision, i.e. `hh:mm:ss.mmm`.
+    #[allow(dead_code)]
+    pub fn to_string_millis(self) -> String {
+        match self {
+            Time::Exactly(t) => {
+                let ms = t.subsec_millis();
+                let hms = Time::Exactly(Duration::from_secs(t.as_secs()));
+                format!("{hms}.{ms:03}")
+            }
+            Time::Forever => self.to_string(),
+        }
+    }
+}
+
+/// Parses the digits after the decimal point of a number of seconds,
+/// e.g. `5` or `500` (both half a second), into nanoseconds.
+fn parse_nanos(fraction: &str) -> Result<u32> {
+    if fraction.is_empty()
+        || fraction.len() > 9
+        || !fraction
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+    {
+        return Err(err!("'{fraction}' is not a valid fraction of a second"));
+    }
+
+    format!("{fraction:0<9}")
+        .parse()
+        .or_wrap_with(|| format!("'{fraction}' is not a number"))
 }
 
 #[cfg(test)]
@@ -113,6 +217,29 @@ mod tests {
 
     use super::*;
 
+    #[test_case(&[], None; "Empty")]
+    #[test_case(&["00:00:42"], Some("00:00:42"); "Single element")]
+    #[test_case(&["00:00:01", "00:00:02"], Some("00:00:02"); "Rounds up")]
+    #[test_case(
+        &["00:10:00", "00:20:00", "01:00:00"], Some("00:30:00");
+        "Multiple elements")]
+    #[test_case(
+        &["00:10:00", ">24h", "00:20:00"], Some("00:15:00");
+        "Excludes >24h")]
+    #[test_case(&[">24h", ">24h"], Some(">24h"); "Only >24h")]
+    fn average_of_slice(slice: &[&str], expected: Option<&str>) -> Result<()> {
+        let times = slice
+            .iter()
+            .map(|str| Time::try_from(*str))
+            .collect::<Result<Vec<_>>>()?;
+
+        let expected = expected
+            .map(Time::try_from)
+            .transpose()?;
+        assert_eq!(Time::average(&times), expected);
+        Ok(())
+    }
+
     #[test_case("00:00:00")]
     #[test_case("00:00:01")]
     #[test_case("00:01:00")]
@@ -125,6 +252,30 @@ mod tests {
         Ok(())
     }
 
+    #[test_case("00:00:01.500", 1_500)]
+    #[test_case("00:00:01.5", 1_500)]
+    #[test_case("00:00:00.001", 1)]
+    #[test_case("01:02:03.000000004", 3_723_000)]
+    fn parse_fractional_seconds(time: &str, millis: u64) -> Result<()> {
+        let Time::Exactly(t) = Time::try_from(time)? else {
+            return Err(err!("Not an exact time: '{time}'"));
+        };
+
+        assert_eq!(t.as_millis(), u128::from(millis));
+        Ok(())
+    }
+
+    #[test_case("00:00:01.500", "00:00:01.500", "00:00:01")]
+    #[test_case("00:00:01.5", "00:00:01.500", "00:00:01")]
+    #[test_case("12:34:56", "12:34:56.000", "12:34:56")]
+    #[test_case(">24h", ">24h", ">24h")]
+    fn to_string_millis(time: &str, millis: &str, secs: &str) -> Result<()> {
+        let time = Time::try_from(time)?;
+        assert_eq!(time.to_string_millis(), millis);
+        assert_eq!(time.to_string(), secs);
+        Ok(())
+    }
+
     #[test_case("     >24h", Time::Forever)]
     #[test_case(" 00:00:00", Time::Exactly(Duration::from_secs(0)))]
     fn formatting_time_supports_padding(expected_output: &str, time: Time) {
@@ -151,12 +302,41 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(">48h", ">48h", Time::Forever)]
+    #[test_case(">1.5d", ">1.5d", Time::Forever)]
+    #[test_case(
+        "30:00:00",
+        ">48h",
+        Time::Exactly(Duration::from_secs(108_000))
+    )]
+    fn parse_time_with_limit(
+        time: &str,
+        limit: &str,
+        expected: Time,
+    ) -> Result<()> {
+        let parsed = Time::parse_with_limit(time, limit)?;
+        assert_eq!(parsed, expected);
+        assert_eq!(parsed.to_string_with_limit(limit), time);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_time_with_limit_rejects_default_limit() {
+        let err = Time::parse_with_limit(">24h", ">48h").unwrap_err();
+        assert!(err.to_string().contains("Invalid time"));
+    }
+
     #[test_case("", "hh:mm:ss"; "empty input")]
     #[test_case("00:00", "hh:mm:ss"; "missing tokens")]
     #[test_case("0A:00:00", "number"; "non-decimal number")]
     #[test_case("00:-1:00", "number"; "negative number")]
     #[test_case("00:60:00", "00..60"; "minutes out of range")]
     #[test_case("00:00:60", "00..60"; "seconds out of range")]
+    #[test_case("00:00:01.", "fraction"; "empty fraction")]
+    #[test_case("00:00:01.5a", "fraction"; "non-decimal fraction")]
+    #[test_case("00:00:01.-5", "fraction"; "negative fraction")]
+    #[test_case("00:00:01.0000000001", "fraction"; "fraction too precise")]
+    #[test_case(">24h.5", "number"; "forever with fraction")]
     fn parse_time_fails(time: &str, expected_err_msg: &str) -> Result<()> {
         let err_msg = Time::try_from(time)
             .unwrap_err()
@@ -4,12 +4,22 @@ use itertools::Itertools;
 
 use crate::{
     ident::Day,
-    leaderboard::{min_med_max::min_med_max_sorted, stats::Stats, Row},
+    leaderboard::{
+        min_med_max::{min_med_max_sorted, Average},
+        stats::Stats,
+        Row,
+    },
 };
 
 #[derive(Debug, Clone, PartialEq, Hash, Eq)]
 pub struct Totals {
     pub rows: [Row<TotalKind>; 3],
+
+    /// The arithmetic mean of time, rank, and score.
+    /// This row is not printed by default.
+    ///
+    /// Note that `>24h` times are excluded when computing the mean time.
+    pub avg: Row<TotalKind>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Hash, Eq, Ord)]
@@ -17,14 +27,15 @@ pub enum TotalKind {
     Min,
     Med,
     Max,
+    Avg,
 }
 
 impl From<&[Row<Day>]> for Totals {
     fn from(rows: &[Row<Day>]) -> Self {
-        // Compute min, median, and max,
+        // Compute min, median, max, and mean,
         // for time, rank, and score,
         // for both parts.
-        let totals_of_part: [Option<[Stats; 3]>; 2] = [0, 1].map(|part| {
+        let totals_of_part: [Option<[Stats; 4]>; 2] = [0, 1].map(|part| {
             let (mut times, mut ranks, mut scores): (Vec<_>, Vec<_>, Vec<_>) =
                 rows.iter()
                     .filter_map(|row| row.parts[part].as_ref())
@@ -43,7 +54,13 @@ impl From<&[Row<Day>]> for Totals {
             let med = Stats::new(t_med, r_med, s_med);
             let max = Stats::new(t_max, r_max, s_max);
 
-            Some([min, med, max])
+            let avg = Stats::new(
+                Average::average(&times)?,
+                Average::average(&ranks)?,
+                Average::average(&scores)?,
+            );
+
+            Some([min, med, max, avg])
         });
 
         // `totals_of_part` is basically an array of “columns”
@@ -55,12 +72,8 @@ impl From<&[Row<Day>]> for Totals {
         // of part one, followed by the values for part two.
         // The second row then contains the median of these values,
         // and the third row their maximum.
-        let totals = [
-            (0, TotalKind::Min),
-            (1, TotalKind::Med),
-            (2, TotalKind::Max),
-        ]
-        .map(|(index, label)| {
+        // The mean is kept in a separate row.
+        let row = |(index, label): (usize, TotalKind)| {
             let columns: [Option<Stats>; 2] = [0, 1].map(|part| {
                 totals_of_part[part]
                     .as_ref()
@@ -71,9 +84,18 @@ impl From<&[Row<Day>]> for Totals {
                 label,
                 parts: columns,
             }
-        });
+        };
+
+        let rows = [
+            (0, TotalKind::Min),
+            (1, TotalKind::Med),
+            (2, TotalKind::Max),
+        ]
+        .map(row);
+
+        let avg = row((3, TotalKind::Avg));
 
-        Self { rows: totals }
+        Self { rows, avg }
     }
 }
 
@@ -85,6 +107,7 @@ impl Display for TotalKind {
             Min => "MIN",
             Med => "MED",
             Max => "MAX",
+            Avg => "AVG",
         };
 
         write!(f, "{label}")
@@ -6,28 +6,42 @@ pub mod runner;
 pub mod solver;
 
 mod cli;
+mod doctor;
 mod downloader;
+mod event_log;
+mod fetcher;
 mod fs;
 mod leaderboard;
 mod parser;
+mod sim;
+mod submitter;
 mod ui;
+mod watcher;
 
 pub use fs::Config;
+#[cfg(feature = "testing")]
+pub use fs::{create_config_for, tempdir};
 pub use ident::{day, year};
 
 use std::{
-    io::Write,
+    fs::File,
+    future::Future,
+    io::{BufWriter, Write},
+    path::Path,
     process::{ExitCode, Termination},
 };
 
+use itertools::Itertools;
 use lazy_errors::{prelude::*, Result};
-use runner::Runner;
-use tokio::sync::mpsc;
+use runner::{QueueDepth, Runner};
+use tokio::sync::{mpsc, watch};
 
 use downloader::Downloader;
-use ident::{Filter, Id};
-use solver::{Event, Parts, Solver};
-use ui::{Summary, Terminated, Ui};
+use event_log::EventLog;
+use ident::{Day, Filter, Id, Part, Year};
+use leaderboard::Layout;
+use solver::{Event, Parts, SolveOptions, Solver, State};
+use ui::{Summary, Terminated, Ui, UiConfig};
 
 use day::*;
 use ident::part::*;
@@ -38,18 +52,60 @@ const SOLVERS: &[Solver] = &[
     solver!(Y21, D01, y21d01::part1, y21d01::part2, y21d01::parse),
     solver!(Y21, D02, y21d02::part1, y21d02::part2),
     solver!(Y21, D03, y21d03::part1, y21d03::part2, y21d03::parse),
-    solver!(Y23, D03, y23d03::part1, y23d03::part2, y23d03::parse),
+    solver!(
+        Y23,
+        D03,
+        y23d03::part1,
+        y23d03::part2,
+        y23d03::parse,
+        tags = ["grid"]
+    ),
     solver!(Y23, D15, y23d15::part1, y23d15::part2, y23d15::parse),
     solver!(Y24, D01, y24d01::part1, y24d01::part2, y24d01::parse),
     solver!(Y24, D02, y24d02::part1, y24d02::part2, y24d02::parse),
     solver!(Y24, D03, y24d03::part1, y24d03::part2, y24d03::parse),
-    solver!(Y24, D04, y24d04::part1, y24d04::part2, y24d04::parse),
+    solver!(
+        Y24,
+        D04,
+        y24d04::part1,
+        y24d04::part2,
+        y24d04::parse,
+        tags = ["grid"]
+    ),
     solver!(Y24, D05, y24d05::part1, y24d05::part2, y24d05::parse),
-    solver!(Y24, D06, y24d06::part1, y24d06::part2, y24d06::parse),
+    solver!(
+        Y24,
+        D06,
+        y24d06::part1,
+        y24d06::part2,
+        y24d06::parse,
+        tags = ["grid", "simulation"]
+    ),
     solver!(Y24, D07, y24d07::part1, y24d07::part2, y24d07::parse),
-    solver!(Y24, D08, y24d08::part1, y24d08::part2, y24d08::parse),
-    solver!(Y24, D14, y24d14::part1, y24d14::part2, y24d14::parse),
-    solver!(Y24, D16, y24d16::part1, y24d16::part2, y24d16::parse),
+    solver!(
+        Y24,
+        D08,
+        y24d08::part1,
+        y24d08::part2,
+        y24d08::parse,
+        tags = ["grid"]
+    ),
+    solver!(
+        Y24,
+        D14,
+        y24d14::part1,
+        y24d14::part2,
+        y24d14::parse,
+        tags = ["simulation"]
+    ),
+    solver!(
+        Y24,
+        D16,
+        y24d16::part1,
+        y24d16::part2,
+        y24d16::parse,
+        tags = ["grid", "pathfinding"]
+    ),
 ];
 
 #[derive(Debug)]
@@ -57,7 +113,9 @@ pub enum ExitStatus {
     AllRunnersSucceeded,
     SomeRunnersFailed,
     AbortedByUser,
+    NoPuzzlesMatched,
     InternalError(Error),
+    NotConfirmed,
 }
 
 impl Termination for ExitStatus {
@@ -66,18 +124,37 @@ impl Termination for ExitStatus {
             ExitStatus::AllRunnersSucceeded => ExitCode::SUCCESS,
             ExitStatus::SomeRunnersFailed => ExitCode::from(1),
             ExitStatus::AbortedByUser => ExitCode::from(2),
+            ExitStatus::NoPuzzlesMatched => ExitCode::from(3),
             ExitStatus::InternalError(_) => ExitCode::from(4),
+            ExitStatus::NotConfirmed => ExitCode::from(5),
         }
     }
 }
 
-impl From<Result<Summary, Terminated>> for ExitStatus {
-    fn from(value: Result<Summary, Terminated>) -> Self {
+impl From<Summary> for ExitStatus {
+    fn from(value: Summary) -> Self {
         match value {
-            Ok(Summary::Success) => ExitStatus::AllRunnersSucceeded,
-            Ok(Summary::SomeRunnersFailed) => ExitStatus::SomeRunnersFailed,
-            Err(Terminated::AbortedByUser) => ExitStatus::AbortedByUser,
-            Err(Terminated::InternalError(e)) => ExitStatus::InternalError(e),
+            Summary::Success => ExitStatus::AllRunnersSucceeded,
+            Summary::SomeRunnersFailed => ExitStatus::SomeRunnersFailed,
+            Summary::NoPuzzlesMatched => ExitStatus::NoPuzzlesMatched,
+        }
+    }
+}
+
+impl From<Terminated> for ExitStatus {
+    fn from(value: Terminated) -> Self {
+        match value {
+            Terminated::AbortedByUser => ExitStatus::AbortedByUser,
+            Terminated::InternalError(e) => ExitStatus::InternalError(e),
+        }
+    }
+}
+
+impl<T: Into<ExitStatus>> From<Result<T, Terminated>> for ExitStatus {
+    fn from(value: Result<T, Terminated>) -> Self {
+        match value {
+            Ok(value) => value.into(),
+            Err(e) => e.into(),
         }
     }
 }
@@ -94,17 +171,42 @@ pub async fn main() -> ExitStatus {
     status
 }
 
-async fn try_main() -> Result<Summary, Terminated> {
+async fn try_main() -> Result<ExitStatus, Terminated> {
     use cli::Command;
     use std::io::stdout;
 
+    let (options, command) = cli::parse_args_from_env_or_exit();
+
     let config = Config::from_env_or_defaults()?;
-    match cli::parse_args_from_env_or_exit() {
+    let config = if options.local_data {
+        config.with_local_data_dir()?
+    } else {
+        config
+    };
+
+    let summary = match command {
         Command::Login => login(config),
         Command::Logout => logout(config),
-        Command::Solve(filter) => run_solvers(config, &filter).await,
-        Command::Stats(filter) => print_stats(&config, &filter, stdout()),
-    }
+        Command::Solve(filter, options, ui_config) => {
+            run_solvers(config, &filter, &options, ui_config).await
+        }
+        Command::Stats(filter, layout, None) => {
+            print_stats(&config, &filter, &layout, stdout())
+        }
+        Command::Stats(filter, layout, Some(path)) => {
+            print_stats_to_file(&config, &filter, &layout, &path)
+        }
+        Command::Submit(id, answer) => submit(&config, id, &answer).await,
+        Command::TestExamples(filter) => {
+            test_examples(&config, &filter, stdout()).await
+        }
+        Command::Doctor => doctor(&config, stdout()),
+        Command::CacheStats => cache_stats(&config, stdout()),
+        Command::Reset(confirmed) => return reset(config, confirmed, stdout()),
+        Command::Where => print_paths(&config, stdout()),
+    }?;
+
+    Ok(summary.into())
 }
 
 fn login(mut config: Config) -> Result<Summary, Terminated> {
@@ -153,33 +255,413 @@ fn logout(mut config: Config) -> Result<Summary, Terminated> {
     Ok(Summary::Success)
 }
 
+async fn submit(
+    config: &Config,
+    id: Id<(Year, Day, Part)>,
+    answer: &str,
+) -> Result<Summary, Terminated> {
+    let verdict = submitter::submit(config, id, answer).await?;
+    println!("{verdict}");
+
+    if verdict.is_correct() {
+        Ok(Summary::Success)
+    } else {
+        Ok(Summary::SomeRunnersFailed)
+    }
+}
+
+/// Checks the user's setup and writes a line to `w` for each check.
+fn doctor(config: &Config, mut w: impl Write) -> Result<Summary, Terminated> {
+    let checks = doctor::check(config);
+    for check in &checks {
+        writeln!(w, "{check}").or_wrap()?;
+    }
+
+    if checks
+        .iter()
+        .any(|c| c.status == doctor::Status::Broken)
+    {
+        Ok(Summary::SomeRunnersFailed)
+    } else {
+        Ok(Summary::Success)
+    }
+}
+
+fn cache_stats(
+    config: &Config,
+    mut w: impl Write,
+) -> Result<Summary, Terminated> {
+    let (count, bytes) = config.size_of_personal_puzzle_inputs()?;
+    let dir = config.personal_puzzle_inputs_dir();
+
+    writeln!(w, "Personal puzzle inputs directory: {}", dir.display())
+        .or_wrap()?;
+    writeln!(w, "Cached personal puzzle inputs: {count} ({bytes} bytes)")
+        .or_wrap()?;
+
+    Ok(Summary::Success)
+}
+
+/// Writes the directories and the session cookie path of `config` to `w`,
+/// one per line, regardless of whether they exist.
+fn print_paths(
+    config: &Config,
+    mut w: impl Write,
+) -> Result<Summary, Terminated> {
+    let cookie = config.session_cookie_path();
+    let inputs = config.personal_puzzle_inputs_dir();
+    let paths = [
+        ("Repository directory", config.repo_dir()),
+        ("Config directory", config.config_dir()),
+        ("Cache directory", config.cache_dir()),
+        ("Personal puzzle inputs", &inputs),
+        ("Data directory", config.data_dir()),
+        ("Session cookie", &cookie),
+    ];
+
+    for (label, path) in paths {
+        writeln!(w, "{label}: {}", path.display()).or_wrap()?;
+    }
+
+    Ok(Summary::Success)
+}
+
+/// Deletes all cached personal puzzle inputs, but only if `confirmed`.
+/// Otherwise, tells the user what would be deleted and how to confirm.
+fn reset(
+    mut config: Config,
+    confirmed: bool,
+    mut w: impl Write,
+) -> Result<ExitStatus, Terminated> {
+    let cached = config.cached_personal_puzzle_inputs_dir();
+    let inputs = config.personal_puzzle_inputs_dir();
+
+    if !confirmed {
+        writeln!(
+            w,
+            "This will delete all cached personal puzzle inputs in {}",
+            cached.display()
+        )
+        .or_wrap()?;
+        writeln!(w, "Run `aoc reset --yes` to confirm.").or_wrap()?;
+        return Ok(ExitStatus::NotConfirmed);
+    }
+
+    let count = config.clear_all_caches()?;
+    writeln!(w, "Deleted {count} cached file(s)").or_wrap()?;
+
+    if inputs != cached {
+        writeln!(w, "Kept personal puzzle inputs in {}", inputs.display())
+            .or_wrap()?;
+    }
+
+    Ok(Summary::Success.into())
+}
+
+/// Runs the solvers on all example puzzle inputs with known answers
+/// and writes a line to `w` for each example, saying whether it passed.
+async fn test_examples(
+    config: &Config,
+    filter: &Filter,
+    mut w: impl Write,
+) -> Result<Summary, Terminated> {
+    let mut summary = Summary::Success;
+
+    for (solver, parts) in filter_puzzles(SOLVERS, filter) {
+        let (y, d) = (solver.year(), solver.day());
+        for label in config.example_answers_labels(y, d)? {
+            let id = Id((y, d));
+            let input = config.read_example_puzzle_input(y, d, &label)?;
+            let expected = config.read_example_answers(y, d, &label)?;
+
+            let events = solve_example(solver.clone(), parts, input).await?;
+            let errors = check_example_answers(&events, parts, expected);
+
+            if errors.is_empty() {
+                writeln!(w, "{id} example {label}: ok").or_wrap()?;
+            } else {
+                let errors = errors.join(", ");
+                writeln!(w, "{id} example {label}: FAILED ({errors})")
+                    .or_wrap()?;
+                summary = Summary::SomeRunnersFailed;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Runs `solver` on `input` and returns all events it sent.
+async fn solve_example(
+    solver: Solver,
+    parts: Parts,
+    input: String,
+) -> Result<Vec<Event>> {
+    tokio::task::spawn_blocking(move || solver.run_blocking(parts, &input))
+        .await
+        .or_wrap_with(|| "Failed to wait for solver")?
+}
+
+/// Compares the answers in `events` to the `expected` answers
+/// of those parts that were selected by `parts`
+/// and returns a message for each mismatch.
+fn check_example_answers(
+    events: &[Event],
+    parts: Parts,
+    expected: (Option<String>, Option<String>),
+) -> Vec<String> {
+    let (p1, p2) = match parts {
+        Parts::First => (expected.0, None),
+        Parts::Second => (None, expected.1),
+        Parts::Both => expected,
+    };
+
+    [(P1, p1), (P2, p2)]
+        .into_iter()
+        .filter_map(|(p, expected)| {
+            let expected = expected?;
+            let actual = events
+                .iter()
+                .find_map(|e| match &e.state {
+                    State::Done(_, result) if e.step == p.into() => {
+                        Some(result)
+                    }
+                    _ => None,
+                });
+
+            match actual {
+                Some(Ok(Some(answer))) if answer.to_string() == expected => {
+                    None
+                }
+                Some(Ok(Some(answer))) => Some(format!(
+                    "part {p}: expected '{expected}', got '{answer}'"
+                )),
+                Some(Ok(None)) => Some(format!("part {p}: no answer")),
+                Some(Err(e)) => Some(format!("part {p}: {e}")),
+                None => Some(format!("part {p}: not solved")),
+            }
+        })
+        .collect()
+}
+
 async fn run_solvers(
     config: Config,
     filter: &Filter,
+    options: &SolveOptions,
+    ui_config: UiConfig,
 ) -> Result<Summary, Terminated> {
+    let unregistered = find_unregistered_puzzles(SOLVERS, filter);
+    print_unregistered_puzzles(&unregistered, std::io::stderr())?;
+
     let puzzles = filter_puzzles(SOLVERS, filter);
+    let puzzles = filter_puzzles_by_tags(puzzles, &options.tags);
+    if puzzles.is_empty() {
+        return print_no_puzzles_matched(SOLVERS, std::io::stderr());
+    }
+
+    let config = match &options.input_dir {
+        Some(dir) => config.with_personal_puzzle_inputs_dir(dir)?,
+        None => config,
+    };
+    config.check_personal_puzzle_inputs_dir()?;
+
+    if options.watch {
+        let [(solver, parts)] = puzzles.as_slice() else {
+            return print_watch_needs_single_puzzle(
+                &puzzles,
+                std::io::stderr(),
+            );
+        };
+
+        let stop = async {
+            let _ = tokio::signal::ctrl_c().await;
+        };
+        return watch(
+            &config,
+            solver,
+            *parts,
+            options,
+            stop,
+            std::io::stdout(),
+        )
+        .await;
+    }
+
+    let event_log = match &options.dump_events {
+        Some(path) => Some(File::create(path).or_wrap_with(|| {
+            format!("Failed to create event log '{}'", path.display())
+        })?),
+        None => None,
+    };
+
+    let ui = Ui::open(puzzles.clone(), options, ui_config)?;
+    let (tx_queue, rx_queue) = watch::channel(QueueDepth::default());
+    let tx_ui = match event_log {
+        Some(file) => EventLog::spawn(ui.tx(), rx_queue, file).tx(),
+        None => ui.tx(),
+    };
+
+    spawn_actors(config, puzzles, options, ui.shutdown(), tx_ui, tx_queue);
+    let report = ui.join().await?;
+
+    println!("{report}");
+    Ok(report.summary())
+}
+
+/// Tells the user that nothing will be solved
+/// and lists the puzzles that `solvers` are available for, grouped by year.
+fn print_no_puzzles_matched(
+    solvers: &[Solver],
+    mut w: impl Write,
+) -> Result<Summary, Terminated> {
+    writeln!(w, "No puzzles match the given filter and tags.").or_wrap()?;
+    writeln!(w, "Available puzzles:").or_wrap()?;
+
+    let by_year = solvers
+        .iter()
+        .map(|s| (s.year(), s.day()))
+        .sorted()
+        .chunk_by(|(y, _)| *y);
+
+    for (y, days) in &by_year {
+        let days = days
+            .map(|(_, d)| Id(d).to_string())
+            .join(" ");
+        writeln!(w, "  {}: {days}", Id(y)).or_wrap()?;
+    }
+
+    Ok(Summary::NoPuzzlesMatched)
+}
+
+/// Returns the puzzles that `filter` names explicitly
+/// (i.e. by year and day) but that there is no solver for.
+fn find_unregistered_puzzles(
+    solvers: &[Solver],
+    filter: &Filter,
+) -> Vec<Id<(Year, Day)>> {
+    filter
+        .year_days()
+        .filter(|&(y, d)| {
+            !solvers
+                .iter()
+                .any(|s| s.year() == y && s.day() == d)
+        })
+        .unique()
+        .sorted()
+        .map(Id)
+        .collect()
+}
+
+/// Warns the user that `unregistered` puzzles won't be solved
+/// because they haven't been implemented (yet), as opposed to
+/// the user having mistyped the filter.
+fn print_unregistered_puzzles(
+    unregistered: &[Id<(Year, Day)>],
+    mut w: impl Write,
+) -> Result<()> {
+    if unregistered.is_empty() {
+        return Ok(());
+    }
+
+    let ids = unregistered.iter().join(" ");
+    writeln!(w, "Warning: No solver available for: {ids}").or_wrap()
+}
+
+/// Tells the user that `--watch` needs a filter
+/// that matches exactly one puzzle and lists the matched `puzzles`.
+fn print_watch_needs_single_puzzle(
+    puzzles: &[(Solver, Parts)],
+    mut w: impl Write,
+) -> Result<Summary, Terminated> {
+    let ids = puzzles
+        .iter()
+        .map(|(solver, _)| solver.id())
+        .join(" ");
+
+    writeln!(w, "Watching requires a filter matching a single puzzle.")
+        .or_wrap()?;
+    writeln!(w, "Matched puzzles: {ids}").or_wrap()?;
 
-    let ui = Ui::open(puzzles.clone())?;
-    spawn_actors(config, puzzles, ui.tx());
-    ui.join().await
+    Ok(Summary::NoPuzzlesMatched)
+}
+
+/// Solves the puzzle of `solver` each time its input changes,
+/// until `stop` completes (e.g. because the user pressed Ctrl-C).
+async fn watch(
+    config: &Config,
+    solver: &Solver,
+    parts: Parts,
+    options: &SolveOptions,
+    stop: impl Future<Output = ()>,
+    w: impl Write,
+) -> Result<Summary, Terminated> {
+    let (y, d) = (solver.year(), solver.day());
+    let read_input = || {
+        let input = config.read_personal_puzzle_input(y, d)?;
+        if options.normalize_input {
+            Ok(input.map(|i| parser::normalize_input(&i).into_owned()))
+        } else {
+            Ok(input)
+        }
+    };
+
+    watcher::watch(
+        solver,
+        parts,
+        || config.personal_puzzle_input_modified(y, d),
+        read_input,
+        watcher::POLL_INTERVAL,
+        stop,
+        w,
+    )
+    .await?;
+
+    Ok(Summary::Success)
 }
 
 fn print_stats(
     config: &Config,
     filters: &Filter,
+    layout: &Layout,
     mut w: impl Write,
 ) -> Result<Summary, Terminated> {
     let mut delim = "";
     for board in leaderboard::parse_leaderboards_from_fs(config, filters)? {
         write!(w, "{delim}").or_wrap()?;
-        write!(w, "{board}").or_wrap()?;
+        write!(w, "{}", board.with_layout(layout)).or_wrap()?;
         delim = "\n=====================================================\n\n";
     }
 
     Ok(Summary::Success)
 }
 
+fn print_stats_to_file(
+    config: &Config,
+    filters: &Filter,
+    layout: &Layout,
+    path: &Path,
+) -> Result<Summary, Terminated> {
+    let file = File::create(path).or_wrap_with(|| {
+        format!("Failed to create output file '{}'", path.display())
+    })?;
+
+    let mut w = BufWriter::new(file);
+    let summary = print_stats(config, filters, layout, &mut w)?;
+    w.flush().or_wrap_with(|| {
+        format!("Failed to write output file '{}'", path.display())
+    })?;
+
+    Ok(summary)
+}
+
 fn filter_puzzles(solvers: &[Solver], filter: &Filter) -> Vec<(Solver, Parts)> {
+    if filter.is_wildcard() {
+        return solvers
+            .iter()
+            .map(|solver| (solver.clone(), Parts::Both))
+            .collect();
+    }
+
     solvers
         .iter()
         .filter_map(|solver| {
@@ -201,25 +683,55 @@ fn filter_puzzles(solvers: &[Solver], filter: &Filter) -> Vec<(Solver, Parts)> {
         .collect()
 }
 
+/// Keeps those puzzles whose solver has at least one of `tags`,
+/// or all puzzles if `tags` is empty.
+fn filter_puzzles_by_tags(
+    puzzles: Vec<(Solver, Parts)>,
+    tags: &[String],
+) -> Vec<(Solver, Parts)> {
+    if tags.is_empty() {
+        return puzzles;
+    }
+
+    puzzles
+        .into_iter()
+        .filter(|(solver, _)| {
+            tags.iter()
+                .any(|tag| solver.has_tag(tag))
+        })
+        .collect()
+}
+
 fn spawn_actors(
     config: Config,
     puzzles: Vec<(Solver, Parts)>,
+    options: &SolveOptions,
+    shutdown: watch::Receiver<bool>,
     tx_ui: mpsc::Sender<Event>,
+    tx_queue: watch::Sender<QueueDepth>,
 ) {
-    let solver = Runner::spawn(tx_ui.clone());
-    let _downloader = Downloader::spawn(config, puzzles, solver.tx(), tx_ui);
+    let solver =
+        Runner::spawn(tx_ui.clone(), options, shutdown.clone(), tx_queue);
+    let _downloader = Downloader::spawn(
+        config,
+        puzzles,
+        options,
+        shutdown,
+        solver.tx(),
+        tx_ui,
+    );
 }
 
 #[cfg(test)]
 mod tests {
-    use std::str::FromStr;
+    use std::{str::FromStr, time::Duration};
 
     use indoc::indoc;
     use itertools::{izip, Itertools};
     use test_case::test_case;
     use tokio_stream::{wrappers::ReceiverStream, StreamExt};
 
-    use ident::{Day, FilterTerm, Id, Year};
+    use ident::FilterTerm;
     use solver::{State, Step};
 
     use super::*;
@@ -243,7 +755,7 @@ mod tests {
     fn mock_ok_2(data: &str) -> Result<usize> {
         // Let's block the thread and async runtime and make our tests slow.
         // Yeah, this is bad, but I'd like to see the tick events get fired.
-        std::thread::sleep(std::time::Duration::from_millis(500));
+        std::thread::sleep(Duration::from_millis(500));
 
         Ok(data.len())
     }
@@ -265,6 +777,16 @@ mod tests {
         (Y21, D02, Parts::Second),
         (Y24, D02, Parts::Second),
     ])]
+    #[test_case(&["d01"], &[
+        (Y21, D01, Parts::Both),
+        (Y24, D01, Parts::Both),
+    ])]
+    #[test_case(&["d03p2", "d15"], &[
+        (Y21, D03, Parts::Second),
+        (Y23, D03, Parts::Second),
+        (Y23, D15, Parts::Both),
+        (Y24, D03, Parts::Second),
+    ])]
     #[test_case(&[], &[
         (Y21, D01, Parts::Both),
         (Y21, D02, Parts::Both),
@@ -302,6 +824,500 @@ mod tests {
         }
     }
 
+    #[test_case("p1", Parts::First)]
+    #[test_case("p2", Parts::Second)]
+    fn init_from_part_filter_selects_all_years(filter: &str, parts: Parts) {
+        let filter = Filter::from(vec![filter.parse().unwrap()]);
+        let puzzles = super::filter_puzzles(SOLVERS, &filter);
+
+        assert_eq!(puzzles.len(), SOLVERS.len());
+        assert!(puzzles.iter().all(|(_, p)| *p == parts));
+
+        let years = puzzles
+            .iter()
+            .map(|(solver, _)| solver.year())
+            .unique()
+            .collect_vec();
+        assert_eq!(years, vec![Y21, Y23, Y24]);
+    }
+
+    #[test_case(&[], 5; "no tags")]
+    #[test_case(&["pathfinding"], 1; "pathfinding")]
+    #[test_case(&["simulation"], 2; "simulation")]
+    #[test_case(&["grid"], 2; "shared tag")]
+    #[test_case(&["pathfinding", "simulation"], 3; "multiple tags")]
+    #[test_case(&["unknown"], 0; "unknown tag")]
+    fn filter_puzzles_by_tags(tags: &[&str], expected: usize) {
+        let solvers = [
+            solver!(Y21, D01, mock_ok_1, mock_ok_1),
+            solver!(Y21, D02, mock_ok_1, mock_ok_1, tags = ["pathfinding"]),
+            solver!(Y24, D03, mock_ok_1, mock_ok_1, tags = ["simulation"]),
+            solver!(
+                Y24,
+                D04,
+                mock_ok_1,
+                mock_ok_1,
+                tags = ["simulation", "grid"]
+            ),
+            solver!(Y24, D05, mock_ok_1, mock_ok_1, tags = ["grid"]),
+        ];
+
+        let tags = tags
+            .iter()
+            .map(|&tag| String::from(tag))
+            .collect_vec();
+
+        let puzzles = super::filter_puzzles(&solvers, &Filter::default());
+        let puzzles = super::filter_puzzles_by_tags(puzzles, &tags);
+
+        assert_eq!(puzzles.len(), expected);
+        if tags.is_empty() {
+            return;
+        }
+
+        for (solver, _) in puzzles {
+            assert!(tags
+                .iter()
+                .any(|tag| solver.has_tag(tag)));
+        }
+    }
+
+    #[test]
+    fn solver_tags() {
+        let tagged = solver!(Y21, D01, mock_ok_1, mock_ok_1, tags = ["a", "b"]);
+        let parsed =
+            solver!(Y21, D01, mock_ok_1, mock_ok_1, mock_prep_ok, tags = ["c"]);
+        let untagged = solver!(Y21, D01, mock_ok_1, mock_ok_1);
+
+        assert_eq!(tagged.tags(), &["a", "b"]);
+        assert_eq!(parsed.tags(), &["c"]);
+        assert!(untagged.tags().is_empty());
+        assert!(!untagged.has_tag("a"));
+    }
+
+    #[test]
+    fn solver_run_blocking() -> Result<()> {
+        let solver = solver!(Y21, D01, mock_ok_1, mock_err, mock_prep_ok);
+        let events = solver.run_blocking(Parts::Both, "mock input")?;
+
+        let mut done = events
+            .into_iter()
+            .filter_map(|event| match event.state {
+                State::Done(_, Ok(None)) => Some((event.step, String::new())),
+                State::Done(_, Ok(Some(answer))) => {
+                    Some((event.step, answer.to_string()))
+                }
+                State::Done(_, Err(e)) => Some((event.step, e.to_string())),
+                _ => None,
+            })
+            .collect_vec();
+        done.sort();
+
+        assert_eq!(done, vec![
+            (Step::Preproc, String::new()),
+            (Step::Part1, String::from("MOCK_PUZZLE_ANSWER")),
+            (Step::Part2, String::from("This mock solver must fail")),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn print_paths() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let config = fs::create_config_for(&tempdir)?;
+
+        let mut buffer = Vec::new();
+        let summary = super::print_paths(&config, &mut buffer)
+            .or_wrap_with(|| "print_paths() failed")?;
+        assert_eq!(summary, Summary::Success);
+
+        let output = String::from_utf8(buffer).unwrap();
+        let lines = output.lines().collect_vec();
+        let tempdir = tempdir.path().display().to_string();
+        let repo_dir = config.repo_dir().display();
+        let inputs = config.personal_puzzle_inputs_dir();
+        let inputs = inputs.display();
+        let cookie = config.session_cookie_path();
+        let cookie = cookie.display();
+
+        assert_eq!(lines.len(), 6);
+        assert_eq!(lines[0], format!("Repository directory: {repo_dir}"));
+        assert_eq!(lines[3], format!("Personal puzzle inputs: {inputs}"));
+        assert_eq!(lines[5], format!("Session cookie: {cookie}"));
+        for line in &lines[1..] {
+            assert!(line.contains(&tempdir), "{line}");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn cache_stats() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let mut config = fs::create_config_for(&tempdir)?;
+        let dir = config.personal_puzzle_inputs_dir();
+        let dir = dir.display();
+
+        let mut buffer = Vec::new();
+        super::cache_stats(&config, &mut buffer)
+            .or_wrap_with(|| "cache_stats() failed")?;
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            format!(
+                "Personal puzzle inputs directory: {dir}\n\
+                 Cached personal puzzle inputs: 0 (0 bytes)\n"
+            )
+        );
+
+        config.save_personal_puzzle_input(Y21, D01, "1234")?;
+        config.save_personal_puzzle_input(Y21, D02, "123456")?;
+
+        let mut buffer = Vec::new();
+        super::cache_stats(&config, &mut buffer)
+            .or_wrap_with(|| "cache_stats() failed")?;
+        let output = String::from_utf8(buffer).unwrap();
+        assert!(
+            output.ends_with("Cached personal puzzle inputs: 2 (10 bytes)\n")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn run_solvers_without_matching_puzzles() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let config = fs::create_config_for(&tempdir)?;
+        let filter = Filter::from(vec!["y22".parse()?]);
+
+        let options = SolveOptions::default();
+        let summary =
+            run_solvers(config, &filter, &options, UiConfig::default())
+                .await
+                .or_wrap_with(|| "run_solvers() failed")?;
+        assert_eq!(summary, Summary::NoPuzzlesMatched);
+
+        let status = ExitStatus::from(Ok(summary));
+        assert_eq!(status.report(), ExitCode::from(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn print_watch_needs_single_puzzle() -> Result<()> {
+        let solvers = [
+            solver!(Y21, D01, mock_ok_1, mock_ok_1),
+            solver!(Y24, D03, mock_ok_1, mock_ok_1),
+        ];
+        let puzzles = filter_puzzles(&solvers, &Filter::default());
+
+        let mut buffer = Vec::new();
+        let summary =
+            super::print_watch_needs_single_puzzle(&puzzles, &mut buffer)
+                .or_wrap_with(|| "print_watch_needs_single_puzzle() failed")?;
+
+        assert_eq!(summary, Summary::NoPuzzlesMatched);
+        assert_eq!(String::from_utf8(buffer).unwrap(), indoc! {"
+            Watching requires a filter matching a single puzzle.
+            Matched puzzles: y21d01 y24d03
+        "});
+
+        Ok(())
+    }
+
+    #[test]
+    fn print_no_puzzles_matched() -> Result<()> {
+        let solvers = [
+            SOLVERS[0].clone(),
+            solver!(Y24, D03, mock_ok_1, mock_ok_1),
+            solver!(Y21, D05, mock_ok_1, mock_ok_1),
+        ];
+
+        let mut buffer = Vec::new();
+        let summary = super::print_no_puzzles_matched(&solvers, &mut buffer)
+            .or_wrap_with(|| "print_no_puzzles_matched() failed")?;
+
+        assert_eq!(summary, Summary::NoPuzzlesMatched);
+        assert_eq!(String::from_utf8(buffer).unwrap(), indoc! {"
+            No puzzles match the given filter and tags.
+            Available puzzles:
+              y21: d01 d05
+              y24: d03
+        "});
+
+        Ok(())
+    }
+
+    #[test_case(&["y24d04"], &[]; "registered")]
+    #[test_case(&["y24", "d09", "p2"], &[]; "wildcards")]
+    #[test_case(&["y24d09"], &["y24d09"]; "unregistered")]
+    #[test_case(&["y24d09p1", "y21d01", "y24d09p2", "y22d25"], &[
+        "y22d25",
+        "y24d09",
+    ]; "mixed")]
+    fn find_unregistered_puzzles(filter: &[&str], expected: &[&str]) {
+        let solvers = [
+            solver!(Y21, D01, mock_ok_1, mock_ok_1),
+            solver!(Y24, D04, mock_ok_1, mock_ok_1),
+        ];
+
+        let filter = Filter::from(
+            filter
+                .iter()
+                .map(|term| term.parse().unwrap())
+                .collect_vec(),
+        );
+
+        let unregistered = super::find_unregistered_puzzles(&solvers, &filter)
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec();
+        assert_eq!(unregistered, expected);
+    }
+
+    #[test]
+    fn print_unregistered_puzzles() -> Result<()> {
+        let unregistered = ["y22d25".parse()?, "y24d09".parse()?];
+
+        let mut buffer = Vec::new();
+        super::print_unregistered_puzzles(&unregistered, &mut buffer)?;
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "Warning: No solver available for: y22d25 y24d09\n"
+        );
+
+        let mut buffer = Vec::new();
+        super::print_unregistered_puzzles(&[], &mut buffer)?;
+        assert!(buffer.is_empty());
+
+        Ok(())
+    }
+
+    #[test_case(Y21, D01, "y21d01")]
+    #[test_case(Y24, D04, "y24d04")]
+    fn solver_id(y: Year, d: Day, expected: &str) {
+        let solver = SOLVERS
+            .iter()
+            .find(|s| s.year() == y && s.day() == d)
+            .unwrap();
+
+        assert_eq!(solver.id(), Id((y, d)));
+        assert_eq!(solver.id().to_string(), expected);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn reset_requires_confirmation() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let mut config = fs::create_config_for(&tempdir)?;
+        config.save_session_cookie("mock cookie")?;
+        config.save_personal_puzzle_input(Y21, D01, "1234")?;
+
+        let mut buffer = Vec::new();
+        let status = super::reset(config.clone(), false, &mut buffer)
+            .or_wrap_with(|| "reset() failed")?;
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(matches!(status, ExitStatus::NotConfirmed));
+        assert!(output.contains("aoc reset --yes"));
+        assert_eq!(status.report(), ExitCode::from(5));
+        assert_eq!(config.count_personal_puzzle_inputs()?, 1);
+
+        let mut buffer = Vec::new();
+        let status = super::reset(config.clone(), true, &mut buffer)
+            .or_wrap_with(|| "reset() failed")?;
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(matches!(status, ExitStatus::AllRunnersSucceeded));
+        assert_eq!(output, "Deleted 1 cached file(s)\n");
+        assert_eq!(config.count_personal_puzzle_inputs()?, 0);
+        assert!(config.read_session_cookie()?.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    fn reset_keeps_inputs_in_other_dir() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let input_dir = fs::tempdir()?;
+        let mut config = fs::create_config_for(&tempdir)?
+            .with_personal_puzzle_inputs_dir(input_dir.path())?;
+        config.save_personal_puzzle_input(Y21, D01, "1234")?;
+
+        let mut buffer = Vec::new();
+        let status = super::reset(config.clone(), true, &mut buffer)
+            .or_wrap_with(|| "reset() failed")?;
+        let output = String::from_utf8(buffer).unwrap();
+
+        assert!(matches!(status, ExitStatus::AllRunnersSucceeded));
+        assert_eq!(
+            output,
+            format!(
+                "Deleted 0 cached file(s)\n\
+                 Kept personal puzzle inputs in {}\n",
+                input_dir.path().display()
+            )
+        );
+        assert_eq!(config.count_personal_puzzle_inputs()?, 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn run_report_counts_mixed_run() -> Result<()> {
+        let solvers = &[
+            solver!(Y21, D01, mock_ok_1, mock_ok_2, mock_prep_ok),
+            solver!(Y21, D02, mock_err, mock_ok_1, mock_prep_ok),
+            solver!(Y21, D03, mock_panic, mock_panic, mock_prep_err),
+            solver!(Y21, D04, mock_ok_1, mock_panic, mock_prep_ok),
+        ];
+
+        let tempdir = fs::tempdir()?;
+        let mut config = fs::create_config_for(&tempdir)?;
+        for d in [D01, D02, D03, D04] {
+            config.save_personal_puzzle_input(Y21, d, "")?;
+        }
+
+        let puzzles = super::filter_puzzles(solvers, &Filter::default());
+        let options = SolveOptions::default();
+
+        let (tx, rx) = mpsc::channel(1);
+        let (_tx_shutdown, shutdown) = watch::channel(false);
+        let (tx_queue, _) = watch::channel(QueueDepth::default());
+        spawn_actors(config, puzzles, &options, shutdown, tx, tx_queue);
+
+        let (report, screen) = ui::run_mock_loop(rx, &options).await;
+        let report = report.or_wrap()?;
+
+        let days = screen
+            .updates
+            .iter()
+            .map(|(d, _)| *d)
+            .unique()
+            .collect_vec();
+        assert_eq!(days.len(), 4);
+        assert_eq!(report, ui::RunReport {
+            solved: 4,
+            failed: 3,
+            skipped_downloads: 4,
+        });
+        assert_eq!(
+            report.to_string(),
+            "4 solved, 3 failed, 4 downloads skipped"
+        );
+        assert_eq!(report.summary(), Summary::SomeRunnersFailed);
+        assert_eq!(ui::RunReport::default().summary(), Summary::Success);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn actors_stop_when_ui_stops_early() -> Result<()> {
+        let solvers = &[
+            solver!(Y21, D01, mock_err, mock_err, mock_prep_ok),
+            solver!(Y21, D02, mock_ok_1, mock_ok_1, mock_prep_ok),
+            solver!(Y21, D03, mock_ok_1, mock_ok_1, mock_prep_ok),
+            solver!(Y21, D04, mock_ok_1, mock_ok_1, mock_prep_ok),
+            solver!(Y21, D05, mock_ok_1, mock_ok_1, mock_prep_ok),
+            solver!(Y21, D06, mock_ok_1, mock_ok_1, mock_prep_ok),
+        ];
+
+        let tempdir = fs::tempdir()?;
+
+        let mut path = tempdir.path().to_path_buf();
+        path.push("personal_puzzle_inputs");
+        std::fs::create_dir(&path).unwrap();
+
+        for d in ["d01", "d02", "d03", "d04", "d05", "d06"] {
+            let mut path = path.clone();
+            path.push(format!("y21{d}_personal_puzzle_input.txt"));
+            std::fs::write(&path, "").unwrap();
+        }
+
+        let config = fs::create_config_for(&tempdir)?;
+        let puzzles = super::filter_puzzles(solvers, &Filter::default());
+
+        let (tx, mut rx) = mpsc::channel(1);
+        let tx_weak = tx.downgrade();
+        let (_tx_shutdown, shutdown) = watch::channel(false);
+        let (tx_queue, _) = watch::channel(QueueDepth::default());
+        spawn_actors(
+            config,
+            puzzles,
+            &SolveOptions::default(),
+            shutdown,
+            tx,
+            tx_queue,
+        );
+
+        // Behave like the UI does in `--fail-fast` mode.
+        let mut got_failure = false;
+        while let Some(event) = rx.recv().await {
+            if matches!(event.state, State::Done(_, Err(_))) {
+                got_failure = true;
+                break;
+            }
+        }
+        rx.close();
+        assert!(got_failure);
+
+        // All actors drop their senders when they shut down.
+        let all_actors_stopped = async {
+            while tx_weak.upgrade().is_some() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+
+        tokio::time::timeout(Duration::from_secs(10), all_actors_stopped)
+            .await
+            .or_wrap_with(|| "Actors did not stop")
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn single_thread_solves_part1_before_part2() -> Result<()> {
+        // Part one is slow, so part two would usually start first.
+        let solvers = &[solver!(Y21, D01, mock_ok_2, mock_ok_1, mock_prep_ok)];
+
+        let tempdir = fs::tempdir()?;
+        let mut config = fs::create_config_for(&tempdir)?;
+        config.save_personal_puzzle_input(Y21, D01, "")?;
+
+        let puzzles = super::filter_puzzles(solvers, &Filter::default());
+        let options = SolveOptions {
+            single_thread: true,
+            ..SolveOptions::default()
+        };
+
+        let (tx, rx) = mpsc::channel(1);
+        let (_tx_shutdown, shutdown) = watch::channel(false);
+        let (tx_queue, _) = watch::channel(QueueDepth::default());
+        spawn_actors(config, puzzles, &options, shutdown, tx, tx_queue);
+        let events: Vec<Event> = ReceiverStream::new(rx).collect().await;
+
+        let position = |step: Step, started: bool| {
+            events
+                .iter()
+                .position(|e| {
+                    e.step == step
+                        && match e.state {
+                            State::Started(_) => started,
+                            State::Done(_, Ok(_)) => !started,
+                            _ => false,
+                        }
+                })
+                .ok_or_else(|| err!("Missing event for {step:?}"))
+        };
+
+        assert!(position(Step::Part1, false)? < position(Step::Part2, true)?);
+        Ok(())
+    }
+
     #[tokio::test]
     #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
     async fn run_loop_sends_events_to_ui() -> Result<()> {
@@ -781,40 +1797,54 @@ mod tests {
         Ok(())
     }
 
-    // TODO: Add macro to generate cases from `const SOLVERS` automatically.
-    #[test_case("y21d01p1")]
-    #[test_case("y21d01p2")]
-    #[test_case("y21d02p1")]
-    #[test_case("y21d02p2")]
-    #[test_case("y21d03p1")]
-    #[test_case("y21d03p2")]
-    #[test_case("y23d03p1")]
-    #[test_case("y23d03p2")]
-    #[test_case("y23d15p1")]
-    #[test_case("y23d15p2")]
-    #[test_case("y24d01p1")]
-    #[test_case("y24d01p2")]
-    #[test_case("y24d02p1")]
-    #[test_case("y24d02p2")]
-    #[test_case("y24d03p1")]
-    #[test_case("y24d03p2")]
-    #[test_case("y24d04p1")]
-    #[test_case("y24d04p2")]
-    #[test_case("y24d05p1")]
-    #[test_case("y24d05p2")]
-    #[test_case("y24d06p1")]
-    #[test_case("y24d06p2")]
-    #[test_case("y24d07p1")]
-    #[test_case("y24d07p2")]
-    #[test_case("y24d08p1")]
-    #[test_case("y24d08p2")]
-    #[test_case("y24d14p1")]
-    #[test_case("y24d14p2")]
-    #[test_case("y24d16p1")]
-    #[test_case("y24d16p2")]
+    /// Returns the IDs of both parts of each puzzle in [`SOLVERS`],
+    /// so new solvers are covered by tests automatically.
+    fn solver_part_ids() -> Vec<Id<(Year, Day, Part)>> {
+        SOLVERS
+            .iter()
+            .flat_map(|s| [P1, P2].map(|p| Id((s.year(), s.day(), p))))
+            .collect()
+    }
+
+    #[test]
+    fn solver_part_ids_roundtrip() -> Result<()> {
+        let ids = solver_part_ids();
+        assert_eq!(ids.len(), 2 * SOLVERS.len());
+
+        for id in ids {
+            let Id((y, d, p)) = id;
+            let filter = id.to_string();
+            assert_eq!(filter.parse::<Id<(Year, Day, Part)>>()?, id);
+            assert_eq!(filter.parse::<FilterTerm>()?, FilterTerm::new(y, d, p));
+        }
+
+        let ids = solver_part_ids()
+            .iter()
+            .map(ToString::to_string)
+            .collect_vec();
+        assert!(ids.contains(&String::from("y24d04p1")));
+        assert!(ids.contains(&String::from("y24d04p2")));
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[ignore] // Requires manually saving the personal puzzles answers before
-    async fn solve_personal_inputs(filter: &str) -> Result<()> {
+    async fn solve_personal_inputs() -> Result<()> {
+        let mut errs =
+            ErrorStash::new(|| "Failed to solve one or more personal inputs");
+
+        for id in solver_part_ids() {
+            solve_personal_input(&id.to_string())
+                .await
+                .or_wrap_with::<Stashable>(|| format!("Failed to solve {id}"))
+                .or_stash(&mut errs);
+        }
+
+        errs.into()
+    }
+
+    async fn solve_personal_input(filter: &str) -> Result<()> {
         let Id((y, d, p)) = filter.parse()?;
         let filter = Filter::from(vec![filter.parse()?]);
 
@@ -838,8 +1868,12 @@ mod tests {
                 }
                 _ => None,
             })
-            .unwrap();
-        assert_eq!(answer.to_string(), expected_answer);
+            .ok_or_else(|| err!("No answer"))?;
+
+        let answer = answer.to_string();
+        if answer != expected_answer {
+            return Err(err!("Expected '{expected_answer}', got '{answer}'"));
+        }
 
         Ok(())
     }
@@ -908,7 +1942,7 @@ mod tests {
               5   00:45:25  6042      0   01:01:39  5242      0
             ---------------------------------------------------
             MIN   00:14:37  5023      0   00:29:07  3395      0
-            MED   00:30:01  5533      0   00:45:23  4319      0
+            MED   00:30:01  5023      0   00:45:23  3395      0
             MAX   00:45:25  6042      0   01:01:39  5242      0
         "};
 
@@ -965,6 +1999,50 @@ mod tests {
         verify_stats(&[], expected)
     }
 
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn test_examples_of_all_solvers_pass() -> Result<()> {
+        let tempdir = fs::tempdir()?;
+        let config = fs::create_config_for(&tempdir)?;
+
+        let mut output = Vec::new();
+        let summary = test_examples(&config, &Filter::default(), &mut output)
+            .await
+            .map_err(|e| err!("{e}"))?;
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(summary, Summary::Success, "{output}");
+        assert!(output.contains("y24d01 example 1: ok\n"));
+        assert!(output.contains("y24d16 example 2: ok\n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_examples_reports_mismatches() -> Result<()> {
+        let solver = solver!(Y21, D02, mock_ok_1, mock_err, mock_prep_ok);
+        let input = String::from("mock input");
+
+        let events = solve_example(solver, Parts::Both, input).await?;
+
+        let expected = (Some(String::from("42")), Some(String::from("42")));
+        let errors = check_example_answers(&events, Parts::Both, expected);
+        assert_eq!(errors, vec![
+            "part 1: expected '42', got 'MOCK_PUZZLE_ANSWER'",
+            "part 2: This mock solver must fail",
+        ]);
+
+        let expected = (Some(String::from("MOCK_PUZZLE_ANSWER")), None);
+        let errors = check_example_answers(&events, Parts::Both, expected);
+        assert!(errors.is_empty());
+
+        let expected = (None, Some(String::from("42")));
+        let errors = check_example_answers(&events, Parts::First, expected);
+        assert!(errors.is_empty());
+
+        Ok(())
+    }
+
     async fn spawn_actors_and_await_events(
         config: Config,
         puzzles: Vec<(Solver, Parts)>,
@@ -972,7 +2050,16 @@ mod tests {
         let (tx, rx) = mpsc::channel(1);
         let rx = ReceiverStream::new(rx);
 
-        spawn_actors(config, puzzles, tx);
+        let (_tx_shutdown, shutdown) = watch::channel(false);
+        let (tx_queue, _) = watch::channel(QueueDepth::default());
+        spawn_actors(
+            config,
+            puzzles,
+            &SolveOptions::default(),
+            shutdown,
+            tx,
+            tx_queue,
+        );
 
         rx.collect().await
     }
@@ -1035,11 +2122,24 @@ mod tests {
 
         let config = fs::create_config_for(&tempdir)?;
         let mut buffer = Vec::new();
-        super::print_stats(&config, &filter, &mut buffer)
+        super::print_stats(&config, &filter, &Layout::default(), &mut buffer)
             .or_wrap_with(|| "print_stats() failed")?;
         let actual_output = String::from_utf8(buffer).unwrap();
 
         assert_eq!(actual_output, expected_output);
+
+        let mut output_file = tempdir.path().to_path_buf();
+        output_file.push("stats.txt");
+        super::print_stats_to_file(
+            &config,
+            &filter,
+            &Layout::default(),
+            &output_file,
+        )
+        .or_wrap_with(|| "print_stats_to_file() failed")?;
+        let file_output = std::fs::read_to_string(&output_file).unwrap();
+
+        assert_eq!(file_output, expected_output);
         Ok(())
     }
 }
@@ -1,3 +1,4 @@
+mod bitgrid;
 mod direction;
 mod grid;
 mod point;
@@ -6,10 +7,14 @@ mod vector;
 
 use core::str::FromStr;
 
+use std::{borrow::Cow, collections::HashSet};
+
 use lazy_errors::{prelude::*, Result};
 use lazy_regex::regex::Regex;
 use rayon::iter::ParallelIterator;
 
+#[allow(unused_imports)]
+pub use bitgrid::BitGrid;
 pub use direction::Direction;
 pub use grid::Grid;
 pub use point::Point;
@@ -35,6 +40,59 @@ pub fn parse_bounds(input: &str) -> Result<Rect> {
     Ok(Rect::new(p, v))
 }
 
+/// Like [`parse_bounds`], but allows lines to differ in length.
+/// The bounds will be as wide as the longest line.
+#[allow(dead_code)]
+pub fn parse_bounds_padded(input: &str) -> Result<Rect> {
+    let y = input.lines().count();
+    let x = input
+        .lines()
+        .map(|line| line.len())
+        .max()
+        .unwrap_or(0);
+
+    let p = Point::new(0, 0);
+    let v = Vector::from_unsigned(y, x)?;
+    Ok(Rect::new(p, v))
+}
+
+/// Returns the columns of the rectangular block of text `input`,
+/// from left to right, each read from top to bottom.
+///
+/// Fails if the lines of `input` differ in length.
+/// Unlike [`parse_bounds`], this counts characters instead of bytes.
+#[allow(dead_code)]
+pub fn transpose_lines(input: &str) -> Result<Vec<String>> {
+    let mut lens: Vec<usize> = input
+        .lines()
+        .map(|line| line.chars().count())
+        .collect();
+
+    lens.dedup();
+    if lens.len() != 1 {
+        return Err(err!("Line lengths differ: {lens:?}"));
+    }
+
+    let mut lines: Vec<_> = input
+        .lines()
+        .map(|line| line.chars())
+        .collect();
+
+    let mut columns = vec![];
+    loop {
+        let column: String = lines
+            .iter_mut()
+            .filter_map(|chars| chars.next())
+            .collect();
+
+        if column.is_empty() {
+            return Ok(columns);
+        }
+
+        columns.push(column);
+    }
+}
+
 /// Parallel variant of [`parse_each`] based on [`rayon::ParallelIterator`].
 pub fn par_parse_each<T, E, S>(
     iter: impl ParallelIterator<Item = S>,
@@ -59,6 +117,45 @@ where
     iter.map(|stringly| parse(stringly))
 }
 
+/// Returns all cells that are part of at least one of the `paths`,
+/// e.g. to find all cells on any shortest path through a maze.
+pub fn collect_path_cells(
+    paths: impl IntoIterator<Item = Vec<(Point, Direction)>>,
+) -> HashSet<Point> {
+    paths
+        .into_iter()
+        .flat_map(|path| path.into_iter().map(|(p, _d)| p))
+        .collect()
+}
+
+/// Calls [`parse`] on each element of the iterator and collects the results.
+///
+/// In contrast to collecting the results of [`parse_each`],
+/// this function does not stop at the first element that fails to parse.
+/// Instead, it returns an error listing all such elements
+/// with their (1-based) line numbers.
+#[allow(dead_code)]
+pub fn parse_all_collecting<T, E, S>(
+    iter: impl Iterator<Item = S>,
+) -> Result<Vec<T>>
+where
+    T: FromStr<Err = E>,
+    E: Into<Stashable>,
+    S: AsRef<str>,
+{
+    let mut errs = ErrorStash::new(|| "Failed to parse one or more lines");
+
+    let values = try2!(iter
+        .enumerate()
+        .map(|(i, stringly)| {
+            parse(stringly)
+                .or_wrap_with::<Stashable>(|| format!("Line {}", i + 1))
+        })
+        .try_collect_or_stash(&mut errs));
+
+    Ok(values)
+}
+
 /// Calls [`parse`] on each `Ok` element of the iterator
 /// and converts `Err` elements from [`std::io::Error`] to `E`.
 ///
@@ -121,6 +218,22 @@ pub fn chars(input: &str) -> impl Iterator<Item = (usize, usize)> {
     (0..n).map(|i| (i, 1))
 }
 
+/// Yields the position and length of each maximal run
+/// of identical characters in `input`, e.g. `..###` yields `(0, 2)` and
+/// `(2, 3)`. This allows parsing each run as a single [`Rect`].
+#[allow(dead_code)]
+pub fn run_matches(input: &str) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let mut chars = input.char_indices().peekable();
+    core::iter::from_fn(move || {
+        let (start, c) = chars.next()?;
+        let mut end = start + c.len_utf8();
+        while let Some((i, _)) = chars.next_if(|&(_, next)| next == c) {
+            end = i + c.len_utf8();
+        }
+        Some((start, end - start))
+    })
+}
+
 // TODO: Use `Pattern` instead of `M` when feature `pattern` (#27721) is stable.
 #[allow(dead_code)]
 pub fn pattern_matches<'a, M, I>(
@@ -176,6 +289,181 @@ pub fn contains_2d(haystack: &str, needle: &str) -> bool {
         })
 }
 
+/// Parses the lines of `input` up to the first empty line
+/// into a [`Grid`] (see [`Grid::from_str`])
+/// and returns the grid together with the rest of `input`
+/// after that empty line, which is left for the caller to parse.
+///
+/// If `input` does not contain an empty line,
+/// all of `input` is parsed into the grid and the rest is empty.
+/// Both `\n` and `\r\n` line breaks are supported.
+#[allow(dead_code)]
+pub fn grid_then<'a, T, E, I>(
+    input: &'a str,
+    matcher: impl FnMut(&'a str) -> I + 'a,
+) -> Result<(Grid<T>, &'a str)>
+where
+    I: Iterator<Item = (usize, usize)> + 'a,
+    T: FromStr<Err = E>,
+    E: Into<Stashable>,
+{
+    let (grid, rest) = ["\n\n", "\r\n\r\n"]
+        .into_iter()
+        .filter_map(|sep| Some((input.find(sep)?, sep.len())))
+        .min()
+        .map(|(i, len)| (&input[..i], &input[i + len..]))
+        .unwrap_or((input, ""));
+
+    let grid = Grid::from_str(grid, matcher)?;
+    Ok((grid, rest))
+}
+
+/// Parses a [`Grid`] of single ASCII digits (`0`-`9`),
+/// e.g. for height maps, where each digit becomes a tile with that value.
+///
+/// Fails if `input` contains any other character (except line breaks)
+/// or if its lines differ in length (see [`parse_bounds`]).
+#[allow(dead_code)]
+pub fn digit_grid(input: &str) -> Result<Grid<u8>> {
+    for (y, line) in input.lines().enumerate() {
+        let non_digit = line
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !c.is_ascii_digit());
+
+        if let Some((x, c)) = non_digit {
+            let p = Point::from_unsigned(y, x)?;
+            return Err(err!("Expected a digit at {p}, got '{c}'"));
+        }
+    }
+
+    Grid::from_str(input, chars)
+}
+
+/// Parses two values separated by `sep`, e.g. `"3,4"` or `"3 4"`.
+///
+/// If `sep` is whitespace, any amount of whitespace separates the values.
+/// Fails if there are not exactly two values or if either fails to parse.
+pub fn parse_pair<T, E>(s: &str, sep: char) -> Result<(T, T)>
+where
+    T: FromStr<Err = E>,
+    E: Into<Stashable>,
+{
+    let tokens: Vec<&str> = if sep.is_whitespace() {
+        s.split_whitespace().collect()
+    } else {
+        s.split(sep).collect()
+    };
+
+    let [l, r]: [&str; 2] = tokens
+        .try_into()
+        .map_err(|tokens: Vec<_>| {
+            let n = tokens.len();
+            err!("Expected two values separated by '{sep}', got {n}: '{s}'")
+        })?;
+
+    let parse = |token: &str| {
+        token
+            .parse()
+            .or_wrap_with(|| format!("Failed to parse '{token}' in '{s}'"))
+    };
+
+    Ok((parse(l)?, parse(r)?))
+}
+
+/// Splits `line` into consecutive columns that are `widths` characters wide,
+/// e.g. to parse tables that are aligned by padding instead of delimited.
+///
+/// Characters after the last column are ignored.
+/// Fails if `line` is shorter than the sum of `widths`.
+#[allow(dead_code)]
+pub fn split_columns<'a>(
+    line: &'a str,
+    widths: &[usize],
+) -> Result<Vec<&'a str>> {
+    let mut columns = Vec::with_capacity(widths.len());
+    let mut rest = line;
+
+    for &width in widths {
+        let end = match rest.char_indices().nth(width) {
+            Some((i, _)) => i,
+            None if rest.chars().count() == width => rest.len(),
+            None => {
+                let expected: usize = widths.iter().sum();
+                let actual = line.chars().count();
+                return Err(err!(
+                    "Line has {actual} characters, expected at least \
+                     {expected}: '{line}'"
+                ));
+            }
+        };
+
+        let (column, tail) = rest.split_at(end);
+        columns.push(column);
+        rest = tail;
+    }
+
+    Ok(columns)
+}
+
+/// Lays out `text` on a canvas of the size of `rect`, e.g. to visualize
+/// a number or a short label in the area occupied by a tile.
+///
+/// Text that fits into a single row is centered on the canvas
+/// (rounding towards the top left). Longer text is wrapped row by row,
+/// starting at the top left. The rest of the canvas is filled with spaces.
+///
+/// Fails if `text` has more characters than `rect` has points.
+#[allow(dead_code)]
+pub fn render_text_on(rect: &Rect, text: &str) -> Result<Vec<Vec<char>>> {
+    let h = usize::try_from(rect.len().y()).unwrap_or(0);
+    let w = usize::try_from(rect.len().x()).unwrap_or(0);
+    let chars: Vec<char> = text.chars().collect();
+
+    if chars.len() > h * w {
+        return Err(err!("Text '{text}' does not fit on {rect}"));
+    }
+
+    let mut canvas = vec![vec![' '; w]; h];
+    if chars.is_empty() {
+        return Ok(canvas);
+    }
+
+    if chars.len() <= w {
+        let y = (h - 1) / 2;
+        let x = (w - chars.len()) / 2;
+        canvas[y][x..x + chars.len()].copy_from_slice(&chars);
+    } else {
+        for (i, c) in chars.into_iter().enumerate() {
+            canvas[i / w][i % w] = c;
+        }
+    }
+
+    Ok(canvas)
+}
+
+/// Removes all carriage returns (`\r`) from `input`
+/// and makes sure that `input` ends with exactly one newline,
+/// unless `input` is empty or consists of line breaks only,
+/// in which case an empty string is returned.
+///
+/// Returns `input` unchanged if it is normalized already.
+pub fn normalize_input(input: &str) -> Cow<'_, str> {
+    let body = input.trim_end_matches(['\r', '\n']);
+    if body.is_empty() {
+        return Cow::Borrowed("");
+    }
+
+    let is_normalized = !body.contains('\r') && input.len() == body.len() + 1;
+    if is_normalized {
+        return Cow::Borrowed(input);
+    }
+
+    let mut normalized = body.replace('\r', "");
+    normalized.push('\n');
+    Cow::Owned(normalized)
+}
+
 fn parse<T, E, S>(text: S) -> Result<T, Error>
 where
     T: FromStr<Err = E>,
@@ -235,6 +523,123 @@ mod tests {
 
     use super::*;
 
+    #[test_case("", 0, 0; "empty")]
+    #[test_case("###\n###\n", 2, 3; "rectangular")]
+    #[test_case("#\n###\n##\n", 3, 3; "ragged")]
+    #[test_case("###\n\n#\n", 3, 3; "empty line")]
+    fn parse_bounds_padded(input: &str, y: isize, x: isize) -> Result<()> {
+        let expected = Rect::new(Point::new(0, 0), Vector::new(y, x));
+        assert_eq!(super::parse_bounds_padded(input)?, expected);
+        Ok(())
+    }
+
+    #[test_case("abc\ndef\n", &["ad", "be", "cf"]; "rectangular")]
+    #[test_case("abc\n", &["a", "b", "c"]; "single line")]
+    #[test_case("a\nb\nc", &["abc"]; "single column")]
+    #[test_case("äb\ncü\n", &["äc", "bü"]; "non-ASCII")]
+    fn transpose_lines(input: &str, expected: &[&str]) -> Result<()> {
+        assert_eq!(super::transpose_lines(input)?, expected);
+        Ok(())
+    }
+
+    #[test_case("abc\nde\n"; "ragged")]
+    #[test_case("äb\nabc\n"; "same bytes, different chars")]
+    fn transpose_lines_fails_on_ragged_input(input: &str) {
+        let err = super::transpose_lines(input).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Line lengths differ"));
+    }
+
+    #[test_case(1, 2, "42", &["42"]; "exact fit")]
+    #[test_case(1, 4, "42", &[" 42 "]; "centered")]
+    #[test_case(3, 3, "7", &["   ", " 7 ", "   "]; "single digit")]
+    #[test_case(2, 3, "1234", &["123", "4  "]; "wrapped")]
+    #[test_case(2, 2, "", &["  ", "  "]; "empty text")]
+    #[test_case(0, 0, "", &[]; "empty rect")]
+    fn render_text_on(
+        dy: isize,
+        dx: isize,
+        text: &str,
+        expected: &[&str],
+    ) -> Result<()> {
+        let rect = Rect::new(Point::new(5, -3), Vector::new(dy, dx));
+        let canvas = super::render_text_on(&rect, text)?;
+        let rows: Vec<String> = canvas
+            .into_iter()
+            .map(String::from_iter)
+            .collect();
+        assert_eq!(rows, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn render_text_on_fails_if_text_does_not_fit() {
+        let rect = Rect::new(Point::new(0, 0), Vector::new(1, 2));
+        let err = super::render_text_on(&rect, "123").unwrap_err();
+        assert!(err.to_string().contains("does not fit"));
+    }
+
+    #[test]
+    fn collect_path_cells() {
+        use Direction::*;
+
+        let paths = vec![
+            vec![(Point::new(0, 0), E), (Point::new(0, 1), E)],
+            vec![(Point::new(0, 0), S), (Point::new(1, 0), E)],
+            vec![(Point::new(0, 0), E), (Point::new(0, 1), S)],
+        ];
+
+        let cells = super::collect_path_cells(paths);
+        assert_eq!(cells.len(), 3);
+        assert!(cells.contains(&Point::new(0, 1)));
+        assert!(cells.contains(&Point::new(1, 0)));
+        assert!(super::collect_path_cells(vec![]).is_empty());
+    }
+
+    #[test]
+    fn parse_all_collecting() -> Result<()> {
+        let numbers: Vec<u8> = super::parse_all_collecting("1\n2\n3".lines())?;
+        assert_eq!(numbers, vec![1, 2, 3]);
+
+        let err =
+            super::parse_all_collecting::<u8, _, _>("1\nx\n3\n256\n5".lines())
+                .unwrap_err();
+
+        let msg = format!("{err:#}");
+        assert!(msg.contains("Failed to parse one or more lines"));
+        assert!(msg.contains("Line 2"));
+        assert!(msg.contains("'x'"));
+        assert!(msg.contains("Line 4"));
+        assert!(msg.contains("'256'"));
+        assert!(!msg.contains("Line 1"));
+        assert!(!msg.contains("Line 3"));
+        assert!(!msg.contains("Line 5"));
+
+        Ok(())
+    }
+
+    #[test_case("", ""; "Empty input")]
+    #[test_case("\r\n\n", ""; "Line breaks only")]
+    #[test_case("a", "a\n"; "Adds missing trailing newline")]
+    #[test_case("a\n", "a\n"; "Keeps single trailing newline")]
+    #[test_case("a\n\n\n", "a\n"; "Removes extra trailing newlines")]
+    #[test_case("a\r\nb\r\n", "a\nb\n"; "Converts CRLF to LF")]
+    #[test_case("a\r\n\r\nb", "a\n\nb\n"; "Keeps empty lines")]
+    #[test_case("a\rb\n", "ab\n"; "Removes stray carriage returns")]
+    fn normalize_input(input: &str, expected: &str) {
+        assert_eq!(super::normalize_input(input), expected);
+    }
+
+    #[test]
+    fn normalize_input_borrows_if_normalized() {
+        let input = "a\r\nb\r\n";
+        assert!(matches!(super::normalize_input(input), Cow::Owned(_)));
+
+        let input = "a\nb\n";
+        assert!(matches!(super::normalize_input(input), Cow::Borrowed(_)));
+    }
+
     #[test]
     fn parse_each() {
         let input: Vec<&str> = vec!["1", "1337"];
@@ -325,6 +730,144 @@ mod tests {
         Ok(())
     }
 
+    #[test_case("", &[])]
+    #[test_case(".", &[(0,1)])]
+    #[test_case("..###.##", &[(0,2), (2,3), (5,1), (6,2)])]
+    #[test_case("aäää", &[(0,1), (1,6)])]
+    fn run_matches(line: &str, expected: &[(usize, usize)]) {
+        let matches: Vec<_> = super::run_matches(line).collect();
+        assert_eq!(&matches, expected);
+    }
+
+    #[test]
+    fn parse_substrs_of_runs() -> Result<()> {
+        let input = "..###.##";
+        let parsed: Vec<(Rect, String)> =
+            super::parse_substrs(input.lines(), super::run_matches)
+                .try_collect()?;
+
+        let rect = |x, dx| Rect::new(Point::new(0, x), Vector::new(1, dx));
+        assert_eq!(parsed, vec![
+            (rect(0, 2), String::from("..")),
+            (rect(2, 3), String::from("###")),
+            (rect(5, 1), String::from(".")),
+            (rect(6, 2), String::from("##")),
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_then() -> Result<()> {
+        let input = indoc! {"\
+            #..
+            .@O
+
+            <^^>
+            vv<
+        "};
+
+        let (grid, rest) = super::grid_then::<char, _, _>(input, chars)?;
+
+        assert!(grid.in_bounds(&Point::new(1, 2)));
+        assert!(!grid.in_bounds(&Point::new(2, 0)));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+        assert_eq!(rest, "<^^>\nvv<\n");
+
+        Ok(())
+    }
+
+    #[test_case("3,4", ',', (3, 4); "comma")]
+    #[test_case("-3,-4", ',', (-3, -4); "negative")]
+    #[test_case("3 4", ' ', (3, 4); "space")]
+    #[test_case("3   4", ' ', (3, 4); "multiple spaces")]
+    #[test_case("3|4", '|', (3, 4); "pipe")]
+    fn parse_pair(input: &str, sep: char, expected: (i32, i32)) -> Result<()> {
+        assert_eq!(super::parse_pair(input, sep)?, expected);
+        Ok(())
+    }
+
+    #[test_case("3,4,5", ',', "got 3"; "three tokens")]
+    #[test_case("3", ',', "got 1"; "one token")]
+    #[test_case("3 4", ',', "got 1"; "wrong separator")]
+    #[test_case("3,x", ',', "Failed to parse 'x'"; "not a number")]
+    fn parse_pair_fails(input: &str, sep: char, expected: &str) {
+        let err = super::parse_pair::<i32, _>(input, sep).unwrap_err();
+        let msg = format!("{err:#}");
+        dbg!(&msg);
+        assert!(msg.contains(expected));
+    }
+
+    #[test_case("abcdef", &[2, 3, 1], &["ab", "cde", "f"]; "exact fit")]
+    #[test_case("abcdef", &[2, 3], &["ab", "cde"]; "ignores rest")]
+    #[test_case(" 12  x", &[3, 3], &[" 12", "  x"]; "padded")]
+    #[test_case("äöü€ab", &[1, 3, 2], &["ä", "öü€", "ab"]; "unicode")]
+    #[test_case("ab", &[0, 2, 0], &["", "ab", ""]; "empty columns")]
+    #[test_case("", &[], &[]; "no columns")]
+    fn split_columns(line: &str, widths: &[usize], expected: &[&str]) {
+        assert_eq!(super::split_columns(line, widths).unwrap(), expected);
+    }
+
+    #[test_case("abcde", &[2, 4]; "too short")]
+    #[test_case("", &[1]; "empty")]
+    #[test_case("äöü", &[4]; "unicode")]
+    fn split_columns_fails(line: &str, widths: &[usize]) {
+        let err = super::split_columns(line, widths).unwrap_err();
+        assert!(err.to_string().starts_with("Line has"));
+    }
+
+    #[test]
+    fn digit_grid() -> Result<()> {
+        let input = indoc! {"\
+            012
+            789
+        "};
+
+        let grid = super::digit_grid(input)?;
+
+        assert!(grid.in_bounds(&Point::new(1, 2)));
+        assert!(!grid.in_bounds(&Point::new(2, 0)));
+        assert_eq!(grid.get_data_at(&Point::new(0, 0)), Some(&0));
+        assert_eq!(grid.get_data_at(&Point::new(0, 2)), Some(&2));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&8));
+
+        Ok(())
+    }
+
+    #[test_case("01\n2.", "(1,1)", "'.'"; "dot")]
+    #[test_case("-1", "(0,0)", "'-'"; "sign")]
+    #[test_case("0ä", "(0,1)", "'ä'"; "non-ascii")]
+    fn digit_grid_fails(input: &str, pos: &str, c: &str) {
+        let err = super::digit_grid(input).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains(pos), "{msg}");
+        assert!(msg.contains(c), "{msg}");
+    }
+
+    #[test]
+    fn grid_then_crlf() -> Result<()> {
+        let input = "#..\r\n.@O\r\n\r\n<^^>\r\nvv<\r\n";
+
+        let (grid, rest) = super::grid_then::<char, _, _>(input, chars)?;
+
+        assert!(grid.in_bounds(&Point::new(1, 2)));
+        assert!(!grid.in_bounds(&Point::new(2, 0)));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+        assert_eq!(rest, "<^^>\r\nvv<\r\n");
+
+        Ok(())
+    }
+
+    #[test]
+    fn grid_then_without_rest() -> Result<()> {
+        let (grid, rest) = super::grid_then::<char, _, _>("#.\n.#\n", chars)?;
+
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'#'));
+        assert_eq!(rest, "");
+
+        Ok(())
+    }
+
     #[test_case("0110", &['1'], &[(1,1), (2,1)])]
     #[test_case("0112", &['1', '2'], &[(1,1), (2,1), (3,1)])]
     fn pattern_matches_chars(
@@ -0,0 +1,173 @@
+use core::{fmt, str::FromStr};
+
+use lazy_errors::{prelude::*, Result};
+
+use super::{Point, Rect};
+
+/// A dense grid of bits inside some [`Rect`] bounds,
+/// e.g. for puzzle inputs that are bitmaps of `#` and `.` characters.
+///
+/// Compared to [`super::Grid`], which stores tiles in a hash map,
+/// this type stores a bit for each [`Point`] inside its bounds.
+/// This makes lookups and updates much faster if most of the bounds
+/// are covered by tiles anyway.
+#[derive(Debug, Clone, Default, PartialEq, Hash, Eq)]
+pub struct BitGrid {
+    bounds: Rect,
+    bits:   Vec<bool>,
+}
+
+impl BitGrid {
+    /// Returns whether the bit at `p` is set,
+    /// or `None` if `p` is outside of the grid's bounds.
+    #[allow(dead_code)]
+    pub fn get(&self, p: &Point) -> Option<bool> {
+        self.index_of(p).map(|i| self.bits[i])
+    }
+
+    /// Sets the bit at `p` to `value`.
+    ///
+    /// Returns an error if `p` is outside of the grid's bounds.
+    #[allow(dead_code)]
+    pub fn set(&mut self, p: &Point, value: bool) -> Result<()> {
+        let i = self.index_of(p).ok_or_else(|| {
+            err!("Point {p} is outside of bounds {}", self.bounds)
+        })?;
+
+        self.bits[i] = value;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .filter(|&&bit| bit)
+            .count()
+    }
+
+    fn index_of(&self, p: &Point) -> Option<usize> {
+        if !self.bounds.contains(p) {
+            return None;
+        }
+
+        let o = self.bounds.pos();
+        let y = usize::try_from(p.y() - o.y()).ok()?;
+        let x = usize::try_from(p.x() - o.x()).ok()?;
+        let x_len = usize::try_from(self.bounds.len().x()).ok()?;
+        Some(y * x_len + x)
+    }
+}
+
+impl FromStr for BitGrid {
+    type Err = Error;
+
+    /// Parses a bitmap where `#` denotes a set bit and `.` an unset bit.
+    fn from_str(input: &str) -> Result<Self> {
+        let bounds = super::parse_bounds(input)?;
+        let bits = input
+            .lines()
+            .flat_map(|line| line.chars())
+            .map(|c| match c {
+                '#' => Ok(true),
+                '.' => Ok(false),
+                c => Err(err!("Unexpected character '{c}' in bitmap")),
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self { bounds, bits })
+    }
+}
+
+impl fmt::Display for BitGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let x_len = usize::try_from(self.bounds.len().x()).unwrap_or(0);
+        if x_len == 0 {
+            return Ok(());
+        }
+
+        let text = self
+            .bits
+            .chunks(x_len)
+            .map(|row| {
+                row.iter()
+                    .map(|&bit| if bit { '#' } else { '.' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        write!(f, "{text}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use test_case::test_case;
+
+    use super::*;
+
+    const INPUT: &str = indoc! {"\
+        #..#
+        .##.
+        ...#"};
+
+    #[test_case(0, 0, Some(true))]
+    #[test_case(0, 1, Some(false))]
+    #[test_case(1, 2, Some(true))]
+    #[test_case(2, 3, Some(true))]
+    #[test_case(2, 2, Some(false))]
+    #[test_case(3, 0, None)]
+    #[test_case(0, 4, None)]
+    #[test_case(-1, 0, None)]
+    fn get(y: isize, x: isize, expected: Option<bool>) -> Result<()> {
+        let grid: BitGrid = INPUT.parse()?;
+        assert_eq!(grid.get(&Point::new(y, x)), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn set() -> Result<()> {
+        let mut grid: BitGrid = INPUT.parse()?;
+        assert_eq!(grid.count_ones(), 5);
+
+        grid.set(&Point::new(0, 0), false)?;
+        grid.set(&Point::new(2, 0), true)?;
+        grid.set(&Point::new(2, 3), true)?;
+
+        assert_eq!(grid.get(&Point::new(0, 0)), Some(false));
+        assert_eq!(grid.get(&Point::new(2, 0)), Some(true));
+        assert_eq!(grid.get(&Point::new(2, 3)), Some(true));
+        assert_eq!(grid.count_ones(), 5);
+        assert_eq!(grid.to_string(), indoc! {"\
+            ...#
+            .##.
+            #..#"});
+
+        let err = grid
+            .set(&Point::new(3, 0), true)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("outside of bounds"));
+
+        Ok(())
+    }
+
+    #[test_case(INPUT; "bitmap")]
+    #[test_case("#"; "single set bit")]
+    #[test_case("."; "single unset bit")]
+    #[test_case(".\n#"; "single column")]
+    fn display_roundtrip(input: &str) -> Result<()> {
+        let grid: BitGrid = input.parse()?;
+        assert_eq!(grid.to_string(), input);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_fails_on_unexpected_character() {
+        let err = "#.\n.O".parse::<BitGrid>().unwrap_err();
+        assert!(err.to_string().contains("'O'"));
+    }
+}
@@ -9,6 +9,11 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// All directions, clockwise starting at [`Direction::N`].
+    ///
+    /// This order is guaranteed to be stable, so algorithms that explore
+    /// neighbors in this order (e.g. [`super::Grid::neighbors`])
+    /// produce reproducible results.
     pub const ALL: [Direction; 4] =
         [Direction::N, Direction::E, Direction::S, Direction::W];
 }
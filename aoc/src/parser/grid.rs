@@ -1,46 +1,256 @@
-use core::fmt;
+use core::{cmp::Reverse, fmt, str::FromStr};
 
-use std::collections::HashSet;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 
-use lazy_errors::Result;
+use lazy_errors::{prelude::*, Result};
 
 use super::{Direction, Point, Rect, Vector};
 
+/// A set of tiles inside some [`Rect`] bounds,
+/// where each tile occupies a single [`Point`] and carries some data `T`.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub struct Grid {
+pub struct Grid<T = ()> {
     bounds: Rect,
-    tiles:  HashSet<Point>,
+    tiles:  HashMap<Point, T>,
 }
 
 impl Grid {
-    pub fn from<T: IntoIterator<Item = Point>>(bounds: Rect, iter: T) -> Self {
+    pub fn from<I: IntoIterator<Item = Point>>(bounds: Rect, iter: I) -> Self {
+        let tiles = iter
+            .into_iter()
+            .map(|p| (p, ()))
+            .collect();
+        Self { bounds, tiles }
+    }
+
+    /// Like [`Grid::from`], but fails if any item of `iter` is an error.
+    /// The error will contain all errors of `iter`, not just the first one.
+    #[allow(dead_code)]
+    pub fn try_from_points<I>(bounds: Rect, iter: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = Result<Point>>,
+    {
+        let mut errs = ErrorStash::new(|| "Failed to create grid from points");
+
+        let points: Vec<Point> = try2!(iter
+            .into_iter()
+            .try_collect_or_stash(&mut errs));
+
+        Ok(Self::from(bounds, points))
+    }
+}
+
+impl<T> Grid<T> {
+    /// Like [`Grid::from`], but each tile carries some data.
+    #[allow(dead_code)]
+    pub fn from_point_data<I>(bounds: Rect, iter: I) -> Self
+    where
+        I: IntoIterator<Item = (Point, T)>,
+    {
         let tiles = iter.into_iter().collect();
         Self { bounds, tiles }
     }
 
-    pub fn from_str<'a, I>(
+    /// Creates a grid whose bounds are determined by the lines of `input`,
+    /// containing a tile for each substring selected by `matcher`.
+    /// The data of each tile is parsed from that substring.
+    pub fn from_str<'a, I, E>(
         input: &'a str,
         matcher: impl FnMut(&'a str) -> I + 'a,
-    ) -> Result<Grid>
+    ) -> Result<Self>
     where
         I: Iterator<Item = (usize, usize)> + 'a,
+        T: FromStr<Err = E>,
+        E: Into<Stashable>,
     {
-        use itertools::Itertools;
-
         let bounds = super::parse_bounds(input)?;
         let tiles = super::parse_substrs(input.lines(), matcher)
-            .map_ok(|(p, _): (Point, char)| p)
             .collect::<Result<_>>()?;
 
         Ok(Self { bounds, tiles })
     }
 
+    /// Like [`Grid::from_str`], but allows lines to differ in length
+    /// instead of failing. The grid's bounds will be as wide as
+    /// the longest line; there are no tiles beyond the end of shorter lines.
+    #[allow(dead_code)]
+    pub fn from_str_padded<'a, I, E>(
+        input: &'a str,
+        matcher: impl FnMut(&'a str) -> I + 'a,
+    ) -> Result<Self>
+    where
+        I: Iterator<Item = (usize, usize)> + 'a,
+        T: FromStr<Err = E>,
+        E: Into<Stashable>,
+    {
+        let bounds = super::parse_bounds_padded(input)?;
+        let tiles = super::parse_substrs(input.lines(), matcher)
+            .collect::<Result<_>>()?;
+
+        Ok(Self { bounds, tiles })
+    }
+
+    #[allow(dead_code)]
+    pub fn get_data_at(&self, p: &Point) -> Option<&T> {
+        self.tiles.get(p)
+    }
+
+    /// Returns an owned copy of the tile at `p`, if any,
+    /// e.g. to record a tile before mutating the grid.
+    #[allow(dead_code)]
+    pub fn snapshot_at(&self, p: &Point) -> Option<(Point, T)>
+    where
+        T: Clone,
+    {
+        self.tiles
+            .get(p)
+            .map(|data| (*p, data.clone()))
+    }
+
+    pub fn in_bounds(&self, p: &Point) -> bool {
+        self.bounds.contains(p)
+    }
+
+    pub fn is_occupied(&self, p: &Point) -> bool {
+        self.tiles.contains_key(p)
+    }
+
+    /// Returns the position and data of each tile, in no particular order.
+    #[allow(dead_code)]
+    pub fn cells(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.tiles
+            .iter()
+            .map(|(&p, data)| (p, data))
+    }
+
+    /// Returns the positions on the outline of the grid's bounds,
+    /// row by row, regardless of whether they contain a tile.
+    /// Each corner is returned only once.
+    #[allow(dead_code)]
+    pub fn edge_cells(&self) -> impl Iterator<Item = Point> {
+        let top = self.bounds.pos().y();
+        let left = self.bounds.pos().x();
+        let bottom = top + self.bounds.len().y() - 1;
+        let right = left + self.bounds.len().x() - 1;
+
+        (top..=bottom).flat_map(move |y| {
+            let step = if y == top || y == bottom {
+                1
+            } else {
+                usize::try_from(right - left)
+                    .unwrap_or(1)
+                    .max(1)
+            };
+
+            (left..=right)
+                .step_by(step)
+                .map(move |x| Point::new(y, x))
+        })
+    }
+
+    /// Returns the positions of all tiles whose data is equal to `data`,
+    /// in no particular order.
+    #[allow(dead_code)]
+    pub fn find_all_positions<'a>(
+        &'a self,
+        data: &'a T,
+    ) -> impl Iterator<Item = Point> + 'a
+    where
+        T: PartialEq,
+    {
+        self.tiles
+            .iter()
+            .filter(move |(_, d)| *d == data)
+            .map(|(&p, _)| p)
+    }
+
+    /// Returns all positions where `self` and `other` differ,
+    /// i.e. where only one of them has a tile or the tiles' data differ,
+    /// together with the data of the tile in `self` and `other`
+    /// (if any), in reading order.
+    ///
+    /// This is useful for debugging, e.g. to find out
+    /// where a simulation step diverges from the expected state.
+    /// The bounds of the grids are not compared.
+    #[allow(dead_code)]
+    pub fn diff<'a>(
+        &'a self,
+        other: &'a Grid<T>,
+    ) -> Vec<(Point, Option<&'a T>, Option<&'a T>)>
+    where
+        T: PartialEq,
+    {
+        let mut diff: Vec<_> = self
+            .tiles
+            .keys()
+            .chain(
+                other
+                    .tiles
+                    .keys()
+                    .filter(|p| !self.is_occupied(p)),
+            )
+            .map(|p| (*p, self.tiles.get(p), other.tiles.get(p)))
+            .filter(|(_, l, r)| l != r)
+            .collect();
+
+        diff.sort_by_key(|(p, ..)| *p);
+        diff
+    }
+
+    /// Returns the grid with its bounds replaced by `bounds`,
+    /// e.g. to embed the grid into a larger area.
+    ///
+    /// Fails if any tile would be outside of `bounds`.
+    #[allow(dead_code)]
+    pub fn with_bounds(self, bounds: Rect) -> Result<Self> {
+        if let Some(p) = self
+            .tiles
+            .keys()
+            .find(|p| !bounds.contains(p))
+        {
+            return Err(err!("Tile {p} is outside of bounds {bounds}"));
+        }
+
+        Ok(Self {
+            bounds,
+            tiles: self.tiles,
+        })
+    }
+
+    /// Returns the tiles adjacent to `p`, in [`Direction::ALL`] order.
     pub fn neighbors(&self, p: &Point) -> Vec<(Point, Direction)> {
-        Direction::ALL
+        self.find_all_neighbors_ordered(p, &Direction::ALL)
+    }
+
+    /// Counts the tiles among the eight positions surrounding `p`
+    /// (see [`Vector::DIRECTIONS`]) whose data satisfies `pred`,
+    /// e.g. for cellular automata. Positions without a tile don't count.
+    #[allow(dead_code)]
+    pub fn count_neighbors8(
+        &self,
+        p: &Point,
+        pred: impl Fn(&T) -> bool,
+    ) -> usize {
+        Vector::DIRECTIONS
+            .iter()
+            .filter_map(|&v| self.tiles.get(&(*p + v)))
+            .filter(|data| pred(data))
+            .count()
+    }
+
+    /// Like [`Grid::neighbors`], but only looks in the directions
+    /// of `order` and returns the tiles in that order,
+    /// e.g. to control the exploration order of a search.
+    pub fn find_all_neighbors_ordered(
+        &self,
+        p: &Point,
+        order: &[Direction],
+    ) -> Vec<(Point, Direction)> {
+        order
             .iter()
             .flat_map(|&d| {
                 let p = *p + Vector::from(d);
-                if self.tiles.contains(&p) {
+                if self.is_occupied(&p) {
                     Some((p, d))
                 } else {
                     None
@@ -48,6 +258,258 @@ impl Grid {
             })
             .collect()
     }
+
+    /// Like [`Grid::neighbors`], but treats the grid as a torus:
+    /// Each neighbor position is wrapped into the grid's bounds
+    /// (see [`Rect::wrap`]) before looking it up, so tiles on one edge
+    /// are adjacent to the tiles on the opposite edge.
+    ///
+    /// This is only meaningful if the grid's bounds are tightly fitted
+    /// to the puzzle's area, as is the case for dense grids parsed
+    /// from the puzzle input. Panics if the bounds are empty.
+    #[allow(dead_code)]
+    pub fn find_all_neighbors_wrapping(
+        &self,
+        p: &Point,
+    ) -> Vec<(Point, Direction)> {
+        Direction::ALL
+            .iter()
+            .flat_map(|&d| {
+                let p = self.bounds.wrap(*p + Vector::from(d));
+                if self.is_occupied(&p) {
+                    Some((p, d))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Moves the tile at `p` one step into each direction of `moves`
+    /// and returns the position the tile ended up at.
+    ///
+    /// A step is blocked if the target position is occupied by another tile
+    /// or lies outside of the grid's bounds. In that case, `on_blocked` is
+    /// called with the grid, the current position of the tile, and the
+    /// direction of the blocked step. The hook may modify the grid,
+    /// e.g. to push other tiles out of the way. If the hook returns `true`
+    /// and the target position is free and in bounds afterwards,
+    /// the tile will be moved. Otherwise, the tile stays where it is
+    /// and the next move will be processed.
+    ///
+    /// Returns an error if there is no tile at `p` or if the hook
+    /// removes the tile that is being moved.
+    #[allow(dead_code)]
+    pub fn apply_moves(
+        &mut self,
+        p: Point,
+        moves: &[Direction],
+        mut on_blocked: impl FnMut(&mut Self, Point, Direction) -> bool,
+    ) -> Result<Point> {
+        if !self.is_occupied(&p) {
+            return Err(err!("No tile at {p}"));
+        }
+
+        let mut p = p;
+        for &d in moves {
+            let next = p + Vector::from(d);
+            if !self.is_free(&next) && !on_blocked(self, p, d) {
+                continue;
+            }
+
+            let Some(data) = self.tiles.remove(&p) else {
+                return Err(err!("Tile at {p} was removed while moving it"));
+            };
+
+            if self.is_free(&next) {
+                self.tiles.insert(next, data);
+                p = next;
+            } else {
+                self.tiles.insert(p, data);
+            }
+        }
+
+        Ok(p)
+    }
+
+    /// Returns whether the tile at `p` could be moved by `v`,
+    /// i.e. whether the target position is in bounds and free,
+    /// without changing the grid.
+    ///
+    /// Returns an error if there is no tile at `p`.
+    #[allow(dead_code)]
+    pub fn can_move(&self, p: &Point, v: Vector) -> Result<bool> {
+        if !self.is_occupied(p) {
+            return Err(err!("No tile at {p}"));
+        }
+
+        Ok(self.is_free(&(*p + v)))
+    }
+
+    /// Removes all tiles whose data matches `pred`
+    /// and returns them, in no particular order.
+    #[allow(dead_code)]
+    pub fn remove_all(&mut self, pred: impl Fn(&T) -> bool) -> Vec<(Point, T)> {
+        let points: Vec<Point> = self
+            .tiles
+            .iter()
+            .filter(|(_, data)| pred(data))
+            .map(|(&p, _)| p)
+            .collect();
+
+        points
+            .into_iter()
+            .filter_map(|p| self.tiles.remove_entry(&p))
+            .collect()
+    }
+
+    /// Removes all tiles whose data is equal to `data`
+    /// and returns them, in no particular order.
+    #[allow(dead_code)]
+    pub fn remove_all_eq(&mut self, data: &T) -> Vec<(Point, T)>
+    where
+        T: PartialEq,
+    {
+        self.remove_all(|d| d == data)
+    }
+
+    /// Finds the cheapest path from `start` to `goal`
+    /// using Dijkstra's algorithm and returns the path
+    /// (including `start` and `goal`) and its total cost.
+    ///
+    /// `successors` returns the points reachable from a given point,
+    /// together with the cost of moving there.
+    /// Successors outside of the grid's bounds are ignored,
+    /// as are paths whose total cost would overflow.
+    /// Returns `None` if `goal` cannot be reached from `start`.
+    #[allow(dead_code)]
+    pub fn dijkstra(
+        &self,
+        start: Point,
+        goal: Point,
+        successors: impl Fn(&Point) -> Vec<(Point, u64)>,
+    ) -> Option<(Vec<Point>, u64)> {
+        // Cheapest cost found so far, and predecessor on that path.
+        let mut costs: HashMap<Point, (u64, Option<Point>)> = HashMap::new();
+        let mut heap = BinaryHeap::new();
+
+        costs.insert(start, (0, None));
+        heap.push((Reverse(0), start));
+
+        while let Some((Reverse(cost), p)) = heap.pop() {
+            if p == goal {
+                let mut path = vec![p];
+                while let Some(&(_, Some(prev))) = costs.get(path.last()?) {
+                    path.push(prev);
+                }
+                path.reverse();
+                return Some((path, cost));
+            }
+
+            match costs.get(&p) {
+                Some(&(known, _)) if known < cost => continue, // Outdated
+                _ => {}
+            }
+
+            for (q, step) in successors(&p) {
+                if !self.in_bounds(&q) {
+                    continue;
+                }
+
+                let Some(next) = cost.checked_add(step) else {
+                    continue;
+                };
+
+                match costs.get(&q) {
+                    Some(&(known, _)) if known <= next => {}
+                    _ => {
+                        costs.insert(q, (next, Some(p)));
+                        heap.push((Reverse(next), q));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns the region of `start`, i.e. the tiles that can be reached
+    /// from `start` via [`Grid::neighbors`] carrying the same data,
+    /// using breadth-first search.
+    ///
+    /// Fails if there is no tile at `start` or if the region has
+    /// more than `max` tiles, instead of filling a runaway region.
+    #[allow(dead_code)]
+    pub fn flood_fill_bounded(
+        &self,
+        start: Point,
+        max: usize,
+    ) -> Result<HashSet<Point>>
+    where
+        T: PartialEq,
+    {
+        let data = self
+            .get_data_at(&start)
+            .ok_or_else(|| err!("No tile at {start}"))?;
+
+        let mut region = HashSet::new();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(p) = queue.pop_front() {
+            if !region.insert(p) {
+                continue;
+            }
+
+            if region.len() > max {
+                return Err(err!(
+                    "Region of {start} has more than {max} tiles"
+                ));
+            }
+
+            for (q, _) in self.neighbors(&p) {
+                if self.tiles.get(&q) == Some(data) && !region.contains(&q) {
+                    queue.push_back(q);
+                }
+            }
+        }
+
+        Ok(region)
+    }
+
+    /// Returns the number of steps on the shortest path from `start`
+    /// to `goal` via [`Grid::neighbors`] whose data is `passable`,
+    /// using breadth-first search.
+    ///
+    /// Every step costs the same; use [`Grid::dijkstra`] otherwise.
+    /// Returns `None` if `goal` cannot be reached from `start`.
+    #[allow(dead_code)]
+    pub fn bfs_distance(
+        &self,
+        start: Point,
+        goal: Point,
+        passable: impl Fn(&T) -> bool,
+    ) -> Option<usize> {
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 0)]);
+
+        while let Some((p, dist)) = queue.pop_front() {
+            if p == goal {
+                return Some(dist);
+            }
+
+            for (q, _) in self.neighbors(&p) {
+                if passable(&self.tiles[&q]) && visited.insert(q) {
+                    queue.push_back((q, dist + 1));
+                }
+            }
+        }
+
+        None
+    }
+
+    fn is_free(&self, p: &Point) -> bool {
+        self.in_bounds(p) && !self.is_occupied(p)
+    }
 }
 
 impl fmt::Display for Grid {
@@ -66,7 +528,10 @@ impl fmt::Display for Grid {
                 .map(|y| {
                     (x_min..(x_min + x_len))
                         .map(|x| {
-                            if self.tiles.contains(&Point::new(y, x)) {
+                            if self
+                                .tiles
+                                .contains_key(&Point::new(y, x))
+                            {
                                 '#'
                             } else {
                                 ' '
@@ -78,3 +543,635 @@ impl fmt::Display for Grid {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+    use itertools::Itertools;
+    use test_case::test_case;
+
+    use super::{super::*, *};
+
+    #[test_case(0, 0, true)]
+    #[test_case(1, 2, true)]
+    #[test_case(-1, 0, false)]
+    #[test_case(0, -1, false)]
+    #[test_case(2, 0, false)]
+    #[test_case(0, 3, false)]
+    fn in_bounds(y: isize, x: isize, expected: bool) -> Result<()> {
+        let grid: Grid<char> = Grid::from_str("#..\n.@.\n", chars)?;
+        assert_eq!(grid.in_bounds(&Point::new(y, x)), expected);
+        Ok(())
+    }
+
+    #[test_case(0, 0, true)]
+    #[test_case(1, 1, true)]
+    #[test_case(0, 1, false)]
+    #[test_case(1, 2, false)]
+    #[test_case(5, 5, false)]
+    fn is_occupied(y: isize, x: isize, expected: bool) -> Result<()> {
+        let tiles = |line| str::match_indices(line, &['#', '@']);
+        let tiles = |line| pattern_matches(line, tiles);
+        let grid: Grid<char> = Grid::from_str("#..\n.@.\n", tiles)?;
+        assert_eq!(grid.is_occupied(&Point::new(y, x)), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn from_str_padded() -> Result<()> {
+        let input = "#.\n.#.#\n\n#\n";
+
+        let err = Grid::<char>::from_str(input, chars).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Line lengths differ"));
+
+        let grid: Grid<char> = Grid::from_str_padded(input, chars)?;
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(4, 4));
+        assert_eq!(grid.bounds, bounds);
+
+        assert_eq!(grid.get_data_at(&Point::new(1, 3)), Some(&'#'));
+        assert_eq!(grid.get_data_at(&Point::new(3, 0)), Some(&'#'));
+        assert!(grid.in_bounds(&Point::new(0, 3)));
+        assert!(!grid.is_occupied(&Point::new(0, 3)));
+        assert!(!grid.is_occupied(&Point::new(2, 0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn eq_ignores_insertion_order() -> Result<()> {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(2, 3));
+        let points = [Point::new(0, 0), Point::new(1, 1), Point::new(0, 2)];
+
+        let forward = Grid::from(bounds, points);
+        let backward = Grid::from(bounds, points.into_iter().rev());
+        assert_eq!(forward, backward);
+
+        let tiles = |line| str::match_indices(line, &['#', 'O']);
+        let tiles = |line| pattern_matches(line, tiles);
+        let mut modified: Grid<char> = Grid::from_str("#O#\n.#O\n", tiles)?;
+        modified.remove_all_eq(&'O');
+        let parsed: Grid<char> = Grid::from_str("#.#\n.#.\n", tiles)?;
+        assert_eq!(modified, parsed);
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_all_neighbors_wrapping() -> Result<()> {
+        let input = indoc! {"\
+            .a.
+            b#c
+            .d.
+        "};
+
+        let tiles = |line| str::match_indices(line, char::is_alphabetic);
+        let tiles = |line| pattern_matches(line, tiles);
+        let grid: Grid<char> = Grid::from_str(input, tiles)?;
+
+        let neighbors = grid.find_all_neighbors_wrapping(&Point::new(0, 1));
+        assert_eq!(neighbors, vec![(Point::new(2, 1), Direction::N)]);
+
+        let neighbors = grid.find_all_neighbors_wrapping(&Point::new(1, 0));
+        assert_eq!(neighbors, vec![(Point::new(1, 2), Direction::W)]);
+
+        let neighbors = grid.find_all_neighbors_wrapping(&Point::new(1, 1));
+        assert_eq!(neighbors, vec![
+            (Point::new(0, 1), Direction::N),
+            (Point::new(1, 2), Direction::E),
+            (Point::new(2, 1), Direction::S),
+            (Point::new(1, 0), Direction::W),
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_all_positions() -> Result<()> {
+        let input = indoc! {"\
+            #.O
+            O@.
+            ..O
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+
+        let mut boxes: Vec<Point> = grid.find_all_positions(&'O').collect();
+        boxes.sort();
+        assert_eq!(boxes, vec![
+            Point::new(0, 2),
+            Point::new(1, 0),
+            Point::new(2, 2),
+        ]);
+
+        assert_eq!(grid.find_all_positions(&'X').count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn from_point_data() {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(2, 3));
+        let grid: Grid<u8> = Grid::from_point_data(bounds, [
+            (Point::new(0, 0), 1),
+            (Point::new(1, 2), 42),
+        ]);
+
+        assert!(grid.in_bounds(&Point::new(1, 2)));
+        assert!(!grid.in_bounds(&Point::new(2, 0)));
+        assert_eq!(grid.get_data_at(&Point::new(0, 0)), Some(&1));
+        assert_eq!(grid.get_data_at(&Point::new(1, 2)), Some(&42));
+        assert_eq!(grid.get_data_at(&Point::new(0, 1)), None);
+    }
+
+    #[test]
+    fn try_from_points() -> Result<()> {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(2, 3));
+        let grid = Grid::try_from_points(bounds, [
+            Ok(Point::new(0, 0)),
+            Ok(Point::new(1, 2)),
+        ])?;
+
+        assert_eq!(
+            grid,
+            Grid::from(bounds, [Point::new(0, 0), Point::new(1, 2)])
+        );
+
+        let err = Grid::try_from_points(bounds, [
+            Ok(Point::new(0, 0)),
+            Err(err!("Mock error 1")),
+            Ok(Point::new(1, 2)),
+            Err(err!("Mock error 2")),
+        ])
+        .unwrap_err();
+
+        let msg = format!("{err:#}");
+        assert!(msg.contains("Failed to create grid from points"));
+        assert!(msg.contains("Mock error 1"));
+        assert!(msg.contains("Mock error 2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn cells() -> Result<()> {
+        let input = indoc! {"\
+            #.
+            .O
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+
+        let mut cells: Vec<(Point, &char)> = grid.cells().collect();
+        cells.sort();
+        assert_eq!(cells, vec![
+            (Point::new(0, 0), &'#'),
+            (Point::new(0, 1), &'.'),
+            (Point::new(1, 0), &'.'),
+            (Point::new(1, 1), &'O'),
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn edge_cells() {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(4, 4));
+        let grid = Grid::from(bounds, []);
+
+        let edges = grid.edge_cells().collect_vec();
+        assert_eq!(edges.len(), 12);
+        assert_eq!(edges.iter().unique().count(), 12);
+        assert!(edges
+            .iter()
+            .all(|p| { p.y() == 0 || p.y() == 3 || p.x() == 0 || p.x() == 3 }));
+    }
+
+    #[test_case(1, 1, 1)]
+    #[test_case(1, 5, 5)]
+    #[test_case(5, 1, 5)]
+    #[test_case(2, 2, 4)]
+    #[test_case(3, 5, 12)]
+    #[test_case(0, 5, 0)]
+    fn edge_cells_count(dy: isize, dx: isize, expected: usize) {
+        let bounds = Rect::new(Point::new(-2, 7), Vector::new(dy, dx));
+        let grid = Grid::from(bounds, []);
+
+        let edges = grid.edge_cells().collect_vec();
+        assert_eq!(edges.len(), expected);
+        assert!(edges.iter().all(|p| bounds.contains(p)));
+    }
+
+    #[test]
+    fn diff() -> Result<()> {
+        let tiles = |line| str::match_indices(line, &['#', 'O']);
+        let tiles = |line| pattern_matches(line, tiles);
+
+        let expected: Grid<char> = Grid::from_str("#O.\n.#O\n", tiles)?;
+        let actual: Grid<char> = Grid::from_str("#O.\n.#.\n", tiles)?;
+        assert!(expected.diff(&expected).is_empty());
+        assert_eq!(expected.diff(&actual), vec![(
+            Point::new(1, 2),
+            Some(&'O'),
+            None
+        )]);
+
+        let actual: Grid<char> = Grid::from_str("#O.\nO#O\n", tiles)?;
+        assert_eq!(expected.diff(&actual), vec![(
+            Point::new(1, 0),
+            None,
+            Some(&'O')
+        )]);
+
+        let actual: Grid<char> = Grid::from_str("#O.\n.OO\n", tiles)?;
+        assert_eq!(expected.diff(&actual), vec![(
+            Point::new(1, 1),
+            Some(&'#'),
+            Some(&'O')
+        )]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn with_bounds_enlarges_grid() -> Result<()> {
+        let grid: Grid<char> = Grid::from_str("#..\n.@.\n", chars)?;
+        let bounds = Rect::new(Point::new(-1, -1), Vector::new(4, 6));
+
+        let grid = grid.with_bounds(bounds)?;
+
+        assert!(grid.in_bounds(&Point::new(-1, -1)));
+        assert!(grid.in_bounds(&Point::new(2, 4)));
+        assert!(!grid.in_bounds(&Point::new(3, 4)));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+        Ok(())
+    }
+
+    #[test]
+    fn with_bounds_fails_if_tile_would_be_orphaned() -> Result<()> {
+        let tiles = |line| str::match_indices(line, &['#', '@']);
+        let tiles = |line| pattern_matches(line, tiles);
+        let grid: Grid<char> = Grid::from_str("#..\n.@.\n", tiles)?;
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(1, 3));
+
+        let err = grid.with_bounds(bounds).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("outside of bounds"));
+        Ok(())
+    }
+
+    #[test]
+    fn dijkstra_finds_cheapest_path() -> Result<()> {
+        let input = indoc! {"\
+            1199
+            9119
+            9511
+            9991
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+        let cost_to_enter = |p: &Point| -> Vec<(Point, u64)> {
+            grid.neighbors(p)
+                .into_iter()
+                .map(|(q, _)| {
+                    let cost = grid
+                        .get_data_at(&q)
+                        .unwrap()
+                        .to_digit(10);
+                    (q, u64::from(cost.unwrap()))
+                })
+                .collect()
+        };
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(3, 3);
+        let (path, cost) = grid
+            .dijkstra(start, goal, cost_to_enter)
+            .unwrap();
+
+        assert_eq!(cost, 6);
+        assert_eq!(path, vec![
+            Point::new(0, 0),
+            Point::new(0, 1),
+            Point::new(1, 1),
+            Point::new(1, 2),
+            Point::new(2, 2),
+            Point::new(2, 3),
+            Point::new(3, 3),
+        ]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn dijkstra_fails_if_goal_is_unreachable() -> Result<()> {
+        let input = indoc! {"\
+            ..#.
+            ..#.
+        "};
+
+        let tiles = |line| str::match_indices(line, '.');
+        let tiles = |line| pattern_matches(line, tiles);
+        let grid: Grid<char> = Grid::from_str(input, tiles)?;
+        let successors = |p: &Point| -> Vec<(Point, u64)> {
+            grid.neighbors(p)
+                .into_iter()
+                .map(|(q, _)| (q, 1))
+                .collect()
+        };
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(1, 3);
+        assert_eq!(grid.dijkstra(start, goal, successors), None);
+
+        let goal = Point::new(1, 1);
+        assert_eq!(
+            grid.dijkstra(start, goal, successors)
+                .unwrap()
+                .1,
+            2
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn dijkstra_skips_paths_whose_cost_overflows() -> Result<()> {
+        let grid: Grid<char> = Grid::from_str("...\n", chars)?;
+        let successors = |p: &Point| -> Vec<(Point, u64)> {
+            grid.neighbors(p)
+                .into_iter()
+                .map(|(q, _)| (q, u64::MAX))
+                .collect()
+        };
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(0, 1);
+        assert_eq!(
+            grid.dijkstra(start, goal, successors)
+                .unwrap()
+                .1,
+            u64::MAX
+        );
+
+        let goal = Point::new(0, 2);
+        assert_eq!(grid.dijkstra(start, goal, successors), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_moves_stops_at_wall() -> Result<()> {
+        let input = indoc! {"\
+            #####
+            #.@.#
+            #####
+        "};
+
+        let tiles = |line| str::match_indices(line, &['#', '@']);
+        let tiles = |line| pattern_matches(line, tiles);
+        let mut grid: Grid<char> = Grid::from_str(input, tiles)?;
+
+        use Direction::*;
+        let moves = [E, E, W, W, W, N];
+        let p = grid.apply_moves(Point::new(1, 2), &moves, |_, _, _| false)?;
+
+        assert_eq!(p, Point::new(1, 1));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+        assert_eq!(grid.get_data_at(&Point::new(1, 2)), None);
+
+        Ok(())
+    }
+
+    #[test_case(Vector::new(0, 0), false; "onto itself")]
+    #[test_case(Vector::new(0, 1), true; "free")]
+    #[test_case(Vector::new(0, -1), false; "collision")]
+    #[test_case(Vector::new(1, 1), false; "out of bounds")]
+    fn can_move(v: Vector, expected: bool) -> Result<()> {
+        let grid =
+            Grid::from(Rect::new(Point::new(0, 0), Vector::new(2, 3)), [
+                Point::new(1, 0),
+                Point::new(1, 1),
+            ]);
+
+        let before = grid.clone();
+        assert_eq!(grid.can_move(&Point::new(1, 1), v)?, expected);
+        assert_eq!(grid, before);
+        Ok(())
+    }
+
+    #[test]
+    fn can_move_fails_without_tile() -> Result<()> {
+        let grid =
+            Grid::from(Rect::new(Point::new(0, 0), Vector::new(2, 3)), []);
+        let err = grid
+            .can_move(&Point::new(0, 0), Vector::E_X)
+            .unwrap_err();
+        assert!(err.to_string().contains("No tile"));
+        Ok(())
+    }
+
+    #[test_case(1, 1, 5; "center")]
+    #[test_case(0, 1, 3; "edge")]
+    #[test_case(0, 0, 2; "corner")]
+    #[test_case(3, 3, 1; "outside")]
+    fn count_neighbors8(y: isize, x: isize, expected: usize) -> Result<()> {
+        let input = indoc! {"\
+            ##.
+            .@#
+            #.#
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+        let p = Point::new(y, x);
+
+        let is_live = |c: &char| *c != '.';
+        assert_eq!(grid.count_neighbors8(&p, is_live), expected);
+        assert_eq!(grid.count_neighbors8(&p, |_| false), 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn bfs_distance() -> Result<()> {
+        let input = indoc! {"\
+            S.#...
+            .##.#.
+            ....#G
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+        let passable = |c: &char| *c != '#';
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(2, 5);
+        assert_eq!(grid.bfs_distance(start, goal, passable), Some(11));
+        assert_eq!(grid.bfs_distance(start, start, passable), Some(0));
+
+        Ok(())
+    }
+
+    #[test]
+    fn bfs_distance_fails_if_goal_is_unreachable() -> Result<()> {
+        let input = indoc! {"\
+            S.#.
+            ..#G
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+        let passable = |c: &char| *c != '#';
+
+        let start = Point::new(0, 0);
+        let goal = Point::new(1, 3);
+        assert_eq!(grid.bfs_distance(start, goal, passable), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn flood_fill_bounded() -> Result<()> {
+        let input = indoc! {"\
+            AAB
+            ABB
+            CCB
+        "};
+
+        let grid: Grid<char> = Grid::from_str(input, chars)?;
+
+        let region = grid.flood_fill_bounded(Point::new(0, 0), 3)?;
+        assert_eq!(
+            region,
+            HashSet::from([
+                Point::new(0, 0),
+                Point::new(0, 1),
+                Point::new(1, 0)
+            ])
+        );
+
+        let region = grid.flood_fill_bounded(Point::new(2, 2), 4)?;
+        assert_eq!(region.len(), 4);
+
+        let err = grid
+            .flood_fill_bounded(Point::new(2, 2), 3)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("more than 3 tiles"));
+
+        let err = grid
+            .flood_fill_bounded(Point::new(5, 5), 3)
+            .unwrap_err();
+        assert!(err.to_string().contains("No tile"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn flood_fill_bounded_stops_at_max() -> Result<()> {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(100, 100));
+        let grid = Grid::from(
+            bounds,
+            (0..100).flat_map(|y| (0..100).map(move |x| Point::new(y, x))),
+        );
+
+        let err = grid
+            .flood_fill_bounded(Point::new(50, 50), 10)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("more than 10 tiles"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_all_neighbors_ordered() -> Result<()> {
+        use Direction::*;
+
+        let tiles = |line| str::match_indices(line, '#');
+        let tiles = |line| pattern_matches(line, tiles);
+        let grid: Grid<char> = Grid::from_str(".#.\n###\n.#.\n", tiles)?;
+        let p = Point::new(1, 1);
+
+        let neighbors = |order: &[Direction]| {
+            grid.find_all_neighbors_ordered(&p, order)
+                .into_iter()
+                .map(|(_, d)| d)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(neighbors(&Direction::ALL), vec![N, E, S, W]);
+        assert_eq!(neighbors(&[W, S, E, N]), vec![W, S, E, N]);
+        assert_eq!(neighbors(&[S, N]), vec![S, N]);
+        assert_eq!(neighbors(&[]), vec![]);
+        assert_eq!(
+            grid.neighbors(&p),
+            grid.find_all_neighbors_ordered(&p, &Direction::ALL)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn snapshot_at() -> Result<()> {
+        let mut grid: Grid<char> = Grid::from_str("#..\n.@.\n", chars)?;
+
+        let p = Point::new(1, 1);
+        let snapshot = grid.snapshot_at(&p);
+        assert_eq!(snapshot, Some((p, '@')));
+        assert_eq!(grid.snapshot_at(&Point::new(5, 5)), None);
+
+        grid.remove_all_eq(&'@');
+
+        assert!(!grid.is_occupied(&p));
+        assert_eq!(snapshot, Some((p, '@')));
+        Ok(())
+    }
+
+    #[test]
+    fn remove_all() -> Result<()> {
+        let input = indoc! {"\
+            #.#
+            .@.
+            #.#
+        "};
+
+        let mut grid: Grid<char> = Grid::from_str(input, chars)?;
+
+        let mut removed = grid.remove_all_eq(&'#');
+        removed.sort_unstable_by_key(|(p, _)| (p.y(), p.x()));
+        assert_eq!(removed, vec![
+            (Point::new(0, 0), '#'),
+            (Point::new(0, 2), '#'),
+            (Point::new(2, 0), '#'),
+            (Point::new(2, 2), '#'),
+        ]);
+
+        for (p, _) in removed {
+            assert_eq!(grid.get_data_at(&p), None);
+        }
+
+        assert_eq!(grid.get_data_at(&Point::new(0, 1)), Some(&'.'));
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+
+        let removed = grid.remove_all(|&c| c != '@');
+        assert_eq!(removed.len(), 4);
+        assert_eq!(grid.get_data_at(&Point::new(0, 1)), None);
+        assert_eq!(grid.get_data_at(&Point::new(1, 1)), Some(&'@'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn apply_moves_fails_if_tile_is_missing() -> Result<()> {
+        let mut grid = Grid::from(Rect::default(), []);
+        let p = Point::new(0, 0);
+        let err = grid
+            .apply_moves(p, &[Direction::E], |_, _, _| true)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "No tile at (0,0)");
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use std::fmt;
+use std::{cmp::Ordering, fmt, str::FromStr};
 
 use lazy_errors::{prelude::*, Result};
 
@@ -23,6 +23,35 @@ impl Point {
     pub fn x(&self) -> isize {
         self.0.x()
     }
+
+    /// Multiplies each coordinate of `self`
+    /// with the corresponding component of `factor`,
+    /// e.g. to stretch a map horizontally.
+    #[allow(dead_code)]
+    pub fn scale(self, factor: Vector) -> Self {
+        Self(self.0.scale(factor))
+    }
+
+    /// Returns whether `other` is one of the four points
+    /// directly above, below, left, or right of `self`.
+    #[allow(dead_code)]
+    pub fn is_adjacent4(&self, other: &Point) -> bool {
+        matches!(self.abs_diff(other), (0, 1) | (1, 0))
+    }
+
+    /// Like [`Point::is_adjacent4`], but also includes
+    /// the four points diagonally adjacent to `self`.
+    #[allow(dead_code)]
+    pub fn is_adjacent8(&self, other: &Point) -> bool {
+        let (dy, dx) = self.abs_diff(other);
+        self != other && dy <= 1 && dx <= 1
+    }
+
+    /// Returns the distance between `self` and `other` along each axis.
+    /// Unlike subtracting the points, this cannot overflow.
+    fn abs_diff(&self, other: &Point) -> (usize, usize) {
+        (self.y().abs_diff(other.y()), self.x().abs_diff(other.x()))
+    }
 }
 
 impl TryFrom<Rect> for Point {
@@ -37,6 +66,22 @@ impl TryFrom<Rect> for Point {
     }
 }
 
+/// Orders points in reading order, i.e. row by row (top to bottom)
+/// and then column by column (left to right).
+///
+/// Note that [`Vector`] is only partially ordered (component-wise).
+impl Ord for Point {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.y(), self.x()).cmp(&(other.y(), other.x()))
+    }
+}
+
+impl PartialOrd for Point {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl std::ops::Sub<Point> for Point {
     type Output = Vector;
 
@@ -67,8 +112,142 @@ impl fmt::Display for Point {
     }
 }
 
+impl FromStr for Point {
+    type Err = Error;
+
+    /// Parses a point from `y,x` (note the order!),
+    /// optionally enclosed in parentheses like in the output of `Display`.
+    fn from_str(s: &str) -> Result<Self> {
+        let err = || err!("Failed to parse point '{s}' (expected 'y,x')");
+
+        let yx = s
+            .strip_prefix('(')
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(s);
+
+        let (y, x) = yx.split_once(',').ok_or_else(err)?;
+        let y = y.parse().or_wrap_with(err)?;
+        let x = x.parse().or_wrap_with(err)?;
+
+        Ok(Point::new(y, x))
+    }
+}
+
 impl From<Point> for Vector {
     fn from(value: Point) -> Self {
         value.0
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(Point::new(2, 3), Vector::new(1, 2), Point::new(2, 6))]
+    #[test_case(Point::new(2, 3), Vector::new(3, 1), Point::new(6, 3))]
+    #[test_case(Point::new(-1, 4), Vector::new(0, -1), Point::new(0, -4))]
+    fn scale(p: Point, factor: Vector, expected: Point) {
+        assert_eq!(p.scale(factor), expected);
+    }
+
+    #[test_case(0, 0, Ordering::Less)]
+    #[test_case(0, 1, Ordering::Less)]
+    #[test_case(0, 2, Ordering::Less)]
+    #[test_case(1, 0, Ordering::Less)]
+    #[test_case(1, 1, Ordering::Equal)]
+    #[test_case(1, 2, Ordering::Greater)]
+    #[test_case(2, 0, Ordering::Greater)]
+    #[test_case(2, 1, Ordering::Greater)]
+    #[test_case(2, 2, Ordering::Greater)]
+    fn cmp(y: isize, x: isize, expectation: Ordering) {
+        let p_l = Point::new(y, x);
+        let p_r = Point::new(1, 1);
+        assert_eq!(p_l.cmp(&p_r), expectation);
+    }
+
+    #[test_case(1, 2, true, true; "orthogonal")]
+    #[test_case(0, 1, true, true; "orthogonal above")]
+    #[test_case(2, 2, false, true; "diagonal")]
+    #[test_case(0, 0, false, true; "diagonal above")]
+    #[test_case(1, 1, false, false; "identical")]
+    #[test_case(1, 3, false, false; "two steps away")]
+    #[test_case(3, 3, false, false; "far away")]
+    fn is_adjacent(y: isize, x: isize, adjacent4: bool, adjacent8: bool) {
+        let p = Point::new(1, 1);
+        let q = Point::new(y, x);
+        assert_eq!(p.is_adjacent4(&q), adjacent4);
+        assert_eq!(p.is_adjacent8(&q), adjacent8);
+        assert_eq!(q.is_adjacent4(&p), adjacent4);
+        assert_eq!(q.is_adjacent8(&p), adjacent8);
+    }
+
+    #[test_case(isize::MIN, isize::MAX, false, false; "opposite extremes")]
+    #[test_case(isize::MAX, isize::MAX - 1, true, true; "orthogonal at max")]
+    #[test_case(isize::MIN + 1, isize::MIN, true, true; "orthogonal at min")]
+    fn is_adjacent_without_overflow(
+        a: isize,
+        b: isize,
+        adjacent4: bool,
+        adjacent8: bool,
+    ) {
+        let p = Point::new(a, 0);
+        let q = Point::new(b, 0);
+        assert_eq!(p.is_adjacent4(&q), adjacent4);
+        assert_eq!(p.is_adjacent8(&q), adjacent8);
+        assert_eq!(q.is_adjacent4(&p), adjacent4);
+        assert_eq!(q.is_adjacent8(&p), adjacent8);
+    }
+
+    #[test_case("12,7", 12, 7)]
+    #[test_case("0,0", 0, 0)]
+    #[test_case("-1,2", -1, 2)]
+    #[test_case("(3,-4)", 3, -4)]
+    fn from_str(s: &str, y: isize, x: isize) -> Result<()> {
+        let p: Point = s.parse()?;
+        assert_eq!(p, Point::new(y, x));
+        assert_eq!(p.y(), y);
+        assert_eq!(p.x(), x);
+        Ok(())
+    }
+
+    #[test_case(0, 0)]
+    #[test_case(12, 7)]
+    #[test_case(-3, 4)]
+    fn display_roundtrip(y: isize, x: isize) -> Result<()> {
+        let p = Point::new(y, x);
+        assert_eq!(p.to_string().parse::<Point>()?, p);
+        Ok(())
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("1"; "single number")]
+    #[test_case("1,2,3"; "three numbers")]
+    #[test_case("a,1"; "not a number")]
+    #[test_case("1, 2"; "whitespace")]
+    #[test_case("(1,2"; "unbalanced parentheses")]
+    fn from_str_fails(s: &str) {
+        let err = s.parse::<Point>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected 'y,x'"));
+    }
+
+    #[test]
+    fn sort_in_reading_order() {
+        let mut points = vec![
+            Point::new(1, 0),
+            Point::new(0, 1),
+            Point::new(-1, 5),
+            Point::new(0, 0),
+        ];
+        points.sort();
+        assert_eq!(points, vec![
+            Point::new(-1, 5),
+            Point::new(0, 0),
+            Point::new(0, 1),
+            Point::new(1, 0),
+        ]);
+    }
+}
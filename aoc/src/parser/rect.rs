@@ -1,5 +1,7 @@
 use std::fmt;
 
+use lazy_errors::{prelude::*, Result};
+
 use super::{Point, Vector};
 
 /// A rectangle that is aligned to the 2D grid,
@@ -23,6 +25,28 @@ impl Rect {
         Self { p, v }
     }
 
+    /// Returns the smallest rectangle that contains all `points`.
+    ///
+    /// Fails if there are no points.
+    #[allow(dead_code)]
+    pub fn from_points(
+        points: impl IntoIterator<Item = Point>,
+    ) -> Result<Self> {
+        let mut points = points.into_iter();
+        let first = points
+            .next()
+            .ok_or_else(|| err!("Cannot fit a rectangle around no points"))?;
+
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            let min = Point::new(min.y().min(p.y()), min.x().min(p.x()));
+            let max = Point::new(max.y().max(p.y()), max.x().max(p.x()));
+            (min, max)
+        });
+
+        let v = Vector::new(max.y() - min.y() + 1, max.x() - min.x() + 1);
+        Ok(Rect::new(min, v))
+    }
+
     pub fn pos(&self) -> Point {
         self.p
     }
@@ -31,6 +55,29 @@ impl Rect {
         self.v
     }
 
+    /// Returns the number of points inside the rectangle,
+    /// saturating at [`usize::MAX`].
+    #[allow(dead_code)]
+    pub fn area(&self) -> usize {
+        let (dy, dx) = self.unsigned_len();
+        dy.saturating_mul(dx)
+    }
+
+    /// Returns the length of the rectangle's outline,
+    /// i.e. twice the sum of its height and its width,
+    /// saturating at [`usize::MAX`].
+    ///
+    /// Returns `0` for empty rectangles.
+    #[allow(dead_code)]
+    pub fn perimeter(&self) -> usize {
+        let (dy, dx) = self.unsigned_len();
+        if dy == 0 || dx == 0 {
+            return 0;
+        }
+
+        dy.saturating_add(dx).saturating_mul(2)
+    }
+
     pub fn contains(&self, &p: &Point) -> bool {
         if self.v.y() == 0 || self.v.x() == 0 {
             return false;
@@ -44,6 +91,79 @@ impl Rect {
         o <= p && p <= q
     }
 
+    /// Maps `p` into the rectangle as if the rectangle was a torus,
+    /// i.e. as if leaving the rectangle on one edge
+    /// would make you enter it again on the opposite edge.
+    ///
+    /// Panics if the rectangle is empty.
+    pub fn wrap(&self, p: Point) -> Point {
+        use num::Integer;
+
+        let o = self.p;
+        let y = (p.y() - o.y()).mod_floor(&self.v.y());
+        let x = (p.x() - o.x()).mod_floor(&self.v.x());
+        Point::new(o.y() + y, o.x() + x)
+    }
+
+    /// Splits the rectangle into the rows above `row`
+    /// and the rows starting at `row`.
+    ///
+    /// Fails if either of the two pieces would be empty.
+    #[allow(dead_code)]
+    pub fn split_at_row(&self, row: isize) -> Result<(Rect, Rect)> {
+        let y = self.p.y();
+        let dy = row - y;
+        if dy < 1 || dy >= self.v.y() {
+            return Err(err!("Cannot split {self} at row {row}"));
+        }
+
+        let top = Rect::new(self.p, Vector::new(dy, self.v.x()));
+        let bottom = Rect::new(
+            Point::new(row, self.p.x()),
+            Vector::new(self.v.y() - dy, self.v.x()),
+        );
+        Ok((top, bottom))
+    }
+
+    /// Splits the rectangle into the columns left of `col`
+    /// and the columns starting at `col`.
+    ///
+    /// Fails if either of the two pieces would be empty.
+    #[allow(dead_code)]
+    pub fn split_at_col(&self, col: isize) -> Result<(Rect, Rect)> {
+        let x = self.p.x();
+        let dx = col - x;
+        if dx < 1 || dx >= self.v.x() {
+            return Err(err!("Cannot split {self} at column {col}"));
+        }
+
+        let left = Rect::new(self.p, Vector::new(self.v.y(), dx));
+        let right = Rect::new(
+            Point::new(self.p.y(), col),
+            Vector::new(self.v.y(), self.v.x() - dx),
+        );
+        Ok((left, right))
+    }
+
+    /// Moves the rectangle as little as possible so that it lies
+    /// completely within `bounds`, e.g. to keep a viewport on the screen.
+    ///
+    /// Returns the rectangle unchanged if it does not fit into `bounds`.
+    #[allow(dead_code)]
+    pub fn clamp_into(&self, bounds: &Rect) -> Rect {
+        if self.v.y() > bounds.v.y() || self.v.x() > bounds.v.x() {
+            return *self;
+        }
+
+        let clamp = |pos: isize, len: isize, min: isize, max_len: isize| {
+            pos.clamp(min, min + max_len - len)
+        };
+
+        let y = clamp(self.p.y(), self.v.y(), bounds.p.y(), bounds.v.y());
+        let x = clamp(self.p.x(), self.v.x(), bounds.p.x(), bounds.v.x());
+        Rect::new(Point::new(y, x), self.v)
+    }
+
     /// Expands the rectangle in all four directions,
     /// without checking for overflows and
     /// without using saturating arithmetic.
@@ -67,6 +187,10 @@ impl Rect {
         let v = Vector::new(dy, dx);
         Rect::new(p, v)
     }
+
+    fn unsigned_len(&self) -> (usize, usize) {
+        (self.v.y().unsigned_abs(), self.v.x().unsigned_abs())
+    }
 }
 
 impl fmt::Display for Rect {
@@ -89,6 +213,61 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn from_points() -> Result<()> {
+        let points = [
+            Point::new(3, -1),
+            Point::new(-2, 4),
+            Point::new(0, 0),
+            Point::new(5, 2),
+        ];
+
+        let rect = Rect::from_points(points)?;
+        assert_eq!(rect, Rect::new(Point::new(-2, -1), Vector::new(8, 6)));
+        assert!(points.iter().all(|p| rect.contains(p)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn from_single_point() -> Result<()> {
+        let p = Point::new(7, 3);
+        let rect = Rect::from_points([p])?;
+        assert_eq!(rect, Rect::new(p, Vector::new(1, 1)));
+        Ok(())
+    }
+
+    #[test]
+    fn from_no_points() {
+        let err = Rect::from_points([]).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot fit a rectangle around no points");
+    }
+
+    #[test_case(2, 3, 2, 3; "inside")]
+    #[test_case(-2, 3, 0, 3; "top")]
+    #[test_case(9, 3, 7, 3; "bottom")]
+    #[test_case(2, -1, 2, 0; "left")]
+    #[test_case(2, 8, 2, 7; "right")]
+    #[test_case(-5, 42, 0, 7; "top right")]
+    fn clamp_into(y: isize, x: isize, y_expected: isize, x_expected: isize) {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(10, 10));
+        let v = Vector::new(3, 3);
+
+        let rect = Rect::new(Point::new(y, x), v);
+        let expected = Rect::new(Point::new(y_expected, x_expected), v);
+        assert_eq!(rect.clamp_into(&bounds), expected);
+    }
+
+    #[test]
+    fn clamp_into_too_large() {
+        let bounds = Rect::new(Point::new(0, 0), Vector::new(10, 10));
+        let rect = Rect::new(Point::new(-1, 5), Vector::new(11, 2));
+        assert_eq!(rect.clamp_into(&bounds), rect);
+
+        let exact = Rect::new(Point::new(-1, 5), Vector::new(10, 10));
+        assert_eq!(exact.clamp_into(&bounds), bounds);
+    }
+
     #[test_case(1, 1, 0, 0, 1, 1, false)]
     #[test_case(1, 1, 1, 1, 1, 1, true)]
     #[test_case(0, 0, 1, 1, 1, 1, false)]
@@ -110,6 +289,87 @@ mod tests {
         assert_eq!(rect.contains(&p), expectation);
     }
 
+    #[test_case(0, 0, 0, 0; "Top left stays")]
+    #[test_case(2, 3, 2, 3; "Bottom right stays")]
+    #[test_case(-1, 0, 2, 0; "Beyond top edge")]
+    #[test_case(3, 0, 0, 0; "Beyond bottom edge")]
+    #[test_case(0, -1, 0, 3; "Beyond left edge")]
+    #[test_case(0, 4, 0, 0; "Beyond right edge")]
+    #[test_case(-7, 10, 2, 2; "Far beyond multiple edges")]
+    fn wrap_at_origin(y: isize, x: isize, wrapped_y: isize, wrapped_x: isize) {
+        let rect = Rect::new(Point::new(0, 0), Vector::new(3, 4));
+        let p = Point::new(y, x);
+        assert_eq!(rect.wrap(p), Point::new(wrapped_y, wrapped_x));
+    }
+
+    #[test_case(1, 2, 1, 2; "Top left stays")]
+    #[test_case(0, 2, 3, 2; "Beyond top edge")]
+    #[test_case(4, 2, 1, 2; "Beyond bottom edge")]
+    #[test_case(1, 1, 1, 4; "Beyond left edge")]
+    #[test_case(3, 5, 3, 2; "Beyond right edge")]
+    fn wrap_with_offset(
+        y: isize,
+        x: isize,
+        wrapped_y: isize,
+        wrapped_x: isize,
+    ) {
+        let rect = Rect::new(Point::new(1, 2), Vector::new(3, 3));
+        let p = Point::new(y, x);
+        assert_eq!(rect.wrap(p), Point::new(wrapped_y, wrapped_x));
+    }
+
+    #[test_case(2, 1, 2, 3; "At first possible row")]
+    #[test_case(3, 2, 3, 2; "In the middle")]
+    #[test_case(4, 3, 4, 1; "At last possible row")]
+    fn split_at_row(row: isize, dy_top: isize, y: isize, dy_bottom: isize) {
+        let rect = Rect::new(Point::new(1, 5), Vector::new(4, 4));
+
+        let (top, bottom) = rect.split_at_row(row).unwrap();
+
+        assert_eq!(top, Rect::new(Point::new(1, 5), Vector::new(dy_top, 4)));
+        assert_eq!(
+            bottom,
+            Rect::new(Point::new(y, 5), Vector::new(dy_bottom, 4))
+        );
+    }
+
+    #[test_case(6, 1, 6, 3; "At first possible column")]
+    #[test_case(7, 2, 7, 2; "In the middle")]
+    #[test_case(8, 3, 8, 1; "At last possible column")]
+    fn split_at_col(col: isize, dx_left: isize, x: isize, dx_right: isize) {
+        let rect = Rect::new(Point::new(1, 5), Vector::new(4, 4));
+
+        let (left, right) = rect.split_at_col(col).unwrap();
+
+        assert_eq!(left, Rect::new(Point::new(1, 5), Vector::new(4, dx_left)));
+        assert_eq!(
+            right,
+            Rect::new(Point::new(1, x), Vector::new(4, dx_right))
+        );
+    }
+
+    #[test_case(-1; "Far beyond top or left edge")]
+    #[test_case(1; "Beyond top or left edge")]
+    #[test_case(2; "Top or left edge")]
+    #[test_case(6; "Beyond bottom or right edge")]
+    #[test_case(10; "Far beyond bottom or right edge")]
+    fn split_fails_if_piece_would_be_empty(at: isize) {
+        let rect = Rect::new(Point::new(2, 2), Vector::new(4, 4));
+        assert!(rect.split_at_row(at).is_err());
+        assert!(rect.split_at_col(at).is_err());
+    }
+
+    #[test_case(1, 1, 1, 4; "1x1")]
+    #[test_case(2, 3, 6, 10; "2x3")]
+    #[test_case(5, 1, 5, 12; "5x1")]
+    #[test_case(0, 3, 0, 0; "empty")]
+    #[test_case(isize::MAX, 4, usize::MAX, usize::MAX; "saturating")]
+    fn area_and_perimeter(dy: isize, dx: isize, area: usize, perimeter: usize) {
+        let rect = Rect::new(Point::new(-1, 2), Vector::new(dy, dx));
+        assert_eq!(rect.area(), area);
+        assert_eq!(rect.perimeter(), perimeter);
+    }
+
     #[test_case(1, 1, 0, 0, 1, 1, 1, 1)]
     #[test_case(1, 1, 1, 1, 0, 0, 3, 3)]
     #[allow(clippy::too_many_arguments)]
@@ -15,14 +15,25 @@ pub struct Vector {
 }
 
 impl Vector {
+    /// The diagonal offsets of [`Vector::DIRECTIONS`], in the same order:
+    /// SE, SW, NW, NE.
+    pub const DIAGONALS: [Vector; 4] = [
+        Vector::new(1, 1),
+        Vector::new(1, -1),
+        Vector::new(-1, -1),
+        Vector::new(-1, 1),
+    ];
+    /// The offsets to all eight neighbors of a [`Point`](super::Point),
+    /// clockwise starting at east (`y` grows downwards):
+    /// E, SE, S, SW, W, NW, N, NE.
     pub const DIRECTIONS: [Vector; 8] = [
         Vector::new(0, 1),
-        Vector::new(1, 0),
         Vector::new(1, 1),
-        Vector::new(0, -1),
-        Vector::new(-1, 0),
+        Vector::new(1, 0),
         Vector::new(1, -1),
+        Vector::new(0, -1),
         Vector::new(-1, -1),
+        Vector::new(-1, 0),
         Vector::new(-1, 1),
     ];
     pub const E_X: Vector = Vector::new(0, 1);
@@ -47,6 +58,28 @@ impl Vector {
     pub fn x(&self) -> isize {
         self.x
     }
+
+    /// Applies `f` to both components.
+    pub fn map(self, f: impl Fn(isize) -> isize) -> Self {
+        Self::new(f(self.y), f(self.x))
+    }
+
+    /// Applies `f` to each pair of corresponding components
+    /// of `self` and `other`.
+    pub fn zip_with(
+        self,
+        other: Self,
+        f: impl Fn(isize, isize) -> isize,
+    ) -> Self {
+        Self::new(f(self.y, other.y), f(self.x, other.x))
+    }
+
+    /// Multiplies each component of `self`
+    /// with the corresponding component of `factor`.
+    #[allow(dead_code)]
+    pub fn scale(self, factor: Self) -> Self {
+        self.zip_with(factor, |a, b| a * b)
+    }
 }
 
 impl From<Direction> for Vector {
@@ -64,10 +97,7 @@ impl std::ops::Neg for Vector {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        Vector {
-            y: -self.y,
-            x: -self.x,
-        }
+        self.map(|c| -c)
     }
 }
 
@@ -75,12 +105,7 @@ impl Add for Vector {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let Vector { y: y_l, x: x_l } = self;
-        let Vector { y: y_r, x: x_r } = rhs;
-        Vector {
-            y: y_l + y_r,
-            x: x_l + x_r,
-        }
+        self.zip_with(rhs, |l, r| l + r)
     }
 }
 
@@ -88,12 +113,7 @@ impl Sub for Vector {
     type Output = Self;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let Self { y: y_l, x: x_l } = self;
-        let Self { y: y_r, x: x_r } = rhs;
-        Self {
-            y: y_l - y_r,
-            x: x_l - x_r,
-        }
+        self.zip_with(rhs, |l, r| l - r)
     }
 }
 
@@ -101,11 +121,7 @@ impl std::ops::Mul<isize> for Vector {
     type Output = Self;
 
     fn mul(self, rhs: isize) -> Self::Output {
-        let Vector { y, x } = self;
-        Vector {
-            y: y * rhs,
-            x: x * rhs,
-        }
+        self.map(|c| c * rhs)
     }
 }
 
@@ -145,6 +161,53 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn directions() {
+        let cardinals: Vec<Vector> = Vector::DIRECTIONS
+            .into_iter()
+            .filter(|v| v.y() == 0 || v.x() == 0)
+            .collect();
+        let diagonals: Vec<Vector> = Vector::DIRECTIONS
+            .into_iter()
+            .filter(|v| v.y() != 0 && v.x() != 0)
+            .collect();
+
+        // Clockwise, starting at east.
+        let mut expected: Vec<Vector> = Direction::ALL
+            .map(Vector::from)
+            .to_vec();
+        expected.rotate_left(1);
+        assert_eq!(cardinals, expected);
+
+        assert_eq!(diagonals, Vector::DIAGONALS);
+        assert_eq!(Vector::DIRECTIONS[1], Vector::new(1, 1));
+        assert!(Vector::DIRECTIONS
+            .iter()
+            .all(|v| v.y().abs() <= 1
+                && v.x().abs() <= 1
+                && *v != Vector::default()));
+    }
+
+    #[test_case(Vector::new(3, -4), Vector::new(3, 4))]
+    #[test_case(Vector::new(-2, 0), Vector::new(2, 0))]
+    #[test_case(Vector::new(0, 0), Vector::new(0, 0))]
+    fn map_abs(v: Vector, expected: Vector) {
+        assert_eq!(v.map(isize::abs), expected);
+    }
+
+    #[test_case(Vector::new(1, 5), Vector::new(3, 2), Vector::new(1, 2))]
+    #[test_case(Vector::new(-1, 0), Vector::new(0, -7), Vector::new(-1, -7))]
+    fn zip_with_min(l: Vector, r: Vector, expected: Vector) {
+        assert_eq!(l.zip_with(r, isize::min), expected);
+    }
+
+    #[test_case(Vector::new(3, 4), Vector::new(1, 2), Vector::new(3, 8))]
+    #[test_case(Vector::new(3, 4), Vector::new(-1, 0), Vector::new(-3, 0))]
+    #[test_case(Vector::new(-2, 5), Vector::new(3, -1), Vector::new(-6, -5))]
+    fn scale(v: Vector, factor: Vector, expected: Vector) {
+        assert_eq!(v.scale(factor), expected);
+    }
+
     #[test_case(0, 0, Some(Ordering::Less))]
     #[test_case(0, 1, Some(Ordering::Less))]
     #[test_case(0, 2, None)]
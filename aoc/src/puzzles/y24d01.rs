@@ -1,26 +1,13 @@
 use lazy_errors::{prelude::*, Result};
 
-pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
-    use core::str::FromStr;
+use crate::parser;
 
+pub fn parse(input: &str) -> Result<(Vec<u64>, Vec<u64>)> {
     let lists: Vec<(u64, u64)> = input
         .lines()
         .map(|line| {
-            let tokens: Vec<u64> = line
-                .split_whitespace()
-                .map(u64::from_str)
-                .collect::<Result<_, _>>()
-                .or_wrap_with(|| format!("Failed to parse line '{line}'"))?;
-
-            let [left, right]: [_; 2] =
-                tokens
-                    .try_into()
-                    .map_err(|tokens: Vec<_>| {
-                        let n = tokens.len();
-                        err!("Expected exactly two numbers, got {n}")
-                    })?;
-
-            Ok((left, right))
+            parser::parse_pair(line, ' ')
+                .or_wrap_with(|| format!("Failed to parse line '{line}'"))
         })
         .collect::<Result<_>>()?;
 
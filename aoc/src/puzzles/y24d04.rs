@@ -33,10 +33,7 @@ pub fn part1(data: &HashMap<Point, char>) -> Result<u32> {
 }
 
 pub fn part2(data: &HashMap<Point, char>) -> Result<u32> {
-    let tl = Vector::new(-1, -1);
-    let tr = Vector::new(-1, 1);
-    let bl = Vector::new(1, -1);
-    let br = Vector::new(1, 1);
+    let [br, bl, tl, tr] = Vector::DIAGONALS;
 
     let sum = data
         .iter()
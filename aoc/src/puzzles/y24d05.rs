@@ -3,6 +3,8 @@ use std::collections::HashMap;
 use itertools::Itertools;
 use lazy_errors::{prelude::*, Result};
 
+use crate::parser;
+
 pub struct PrintQueue {
     rules: HashMap<u8, Vec<u8>>,
     good:  Vec<Vec<u8>>,
@@ -15,16 +17,8 @@ pub fn parse(input: &str) -> Result<PrintQueue> {
     let rules = (&mut lines)
         .take_while(|line| !line.is_empty())
         .map(|line| {
-            let [l, r] = line
-                .split('|')
-                .collect::<Vec<_>>()
-                .try_into()
-                .map_err(|_| err!("Invalid line: '{line}'"))?;
-
-            let l = parse_page_number(l)?;
-            let r = parse_page_number(r)?;
-
-            Ok((l, r))
+            parser::parse_pair(line, '|')
+                .or_wrap_with(|| format!("Invalid line: '{line}'"))
         })
         .collect::<Result<Vec<_>>>()?
         .into_iter()
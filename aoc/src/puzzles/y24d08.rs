@@ -51,8 +51,8 @@ where
 {
     Ok(input
         .antennas
-        .iter()
-        .flat_map(|(_kind, positions)| {
+        .values()
+        .flat_map(|positions| {
             combinations(positions)
                 .flat_map(|(&a, &b)| nodes(a, b, &input.bounds))
         })
@@ -85,17 +85,8 @@ impl core::str::FromStr for Robot {
 }
 
 fn parse_coords(s: &str) -> Result<(isize, isize)> {
-    use core::str::FromStr;
-
-    let err = || err!("Failed to parse coordinates '{s}'");
-
-    let [x, y] = s
-        .split(',')
-        .map(|k| isize::from_str(k).or_wrap_with(err))
-        .collect::<Result<Vec<_>>>()?
-        .try_into()
-        .map_err(|_| err())?;
-
+    // The puzzle input is in `x,y` order while `Point` expects `y,x`.
+    let (x, y) = parser::parse_pair(s, ',')?;
     Ok((y, x))
 }
 
@@ -128,20 +119,12 @@ fn part1_impl(input: &[Robot], bounds: &Rect) -> Result<usize> {
 fn move_robots<'a>(
     robots: &'a [Robot],
     steps: usize,
-    bounds: &Rect,
+    bounds: &'a Rect,
 ) -> impl Iterator<Item = Point> + 'a {
-    use num::Integer;
-
     let steps = isize::try_from(steps).unwrap();
-    let y_len = bounds.len().y();
-    let x_len = bounds.len().x();
-
-    robots.iter().map(move |r| {
-        let p = r.p + (r.v * steps);
-        let (y, x) = (p.y(), p.x());
-        let (y, x) = (y.mod_floor(&y_len), x.mod_floor(&x_len));
-        Point::new(y, x)
-    })
+    robots
+        .iter()
+        .map(move |r| bounds.wrap(r.p + (r.v * steps)))
 }
 
 #[cfg(test)]
@@ -4,7 +4,7 @@ use pathfinding::prelude::*;
 use crate::parser::{self, Direction, Grid, Point};
 
 pub struct Input {
-    grid: Grid,
+    grid: Grid<char>,
     s:    Point,
     e:    Point,
 }
@@ -42,15 +42,10 @@ pub fn part2(input: &Input) -> Result<usize> {
         |&(p, _d)| p == input.e,
     )
     .ok_or_else(|| err!("Failed to find any path"))
-    .map(|(paths, _cost)| {
-        paths
-            .flat_map(|path| path.into_iter().map(|(p, _d)| p))
-            .collect::<std::collections::HashSet<Point>>()
-            .len()
-    })
+    .map(|(paths, _cost)| parser::collect_path_cells(paths).len())
 }
 
-fn parse_grid(input: &str) -> Result<Grid> {
+fn parse_grid(input: &str) -> Result<Grid<char>> {
     let tiles = |line| str::match_indices(line, &['.', 'S', 'E']);
     let tiles = |line| parser::pattern_matches(line, tiles);
 
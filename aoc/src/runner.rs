@@ -1,30 +1,50 @@
 use std::{
+    fmt,
     panic::{catch_unwind, UnwindSafe},
+    sync::Arc,
     time::{Duration, Instant},
 };
 
 use lazy_errors::{prelude::*, Result};
+use rayon::{ThreadPool, ThreadPoolBuilder};
 use tokio::{
-    sync::{mpsc, oneshot},
-    task,
+    sync::{mpsc, oneshot, watch},
+    task, time,
 };
 
 use crate::{
     ident::{Day, Id, Part, Year},
-    solver::{num_threads, Event, Parts, PuzzleAnswer, Solver, State, Step},
+    parser,
+    solver::{
+        num_threads, Event, Parts, PuzzleAnswer, SolveOptions, Solver, State,
+        Step,
+    },
 };
 
 pub type Input = String;
 
+/// How often [`Runner`] samples the [`QueueDepth`] of its input queue.
+const QUEUE_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
 pub struct Runner {
     tx: mpsc::Sender<(Solver, Parts, Input)>,
 }
 
 impl Runner {
-    pub fn spawn(tx_ui: mpsc::Sender<Event>) -> Self {
+    pub fn spawn(
+        tx_ui: mpsc::Sender<Event>,
+        options: &SolveOptions,
+        shutdown: watch::Receiver<bool>,
+        tx_queue: watch::Sender<QueueDepth>,
+    ) -> Self {
         // Ensure there is enough work available.
         let (tx, rx) = mpsc::channel(num_threads());
-        task::spawn(run_actor(rx, tx_ui));
+        task::spawn(run_actor(rx, tx_ui, options.clone(), shutdown));
+        task::spawn(sample_queue_depth(
+            tx.downgrade(),
+            tx_queue,
+            QUEUE_SAMPLE_INTERVAL,
+        ));
         Self { tx }
     }
 
@@ -33,36 +53,126 @@ impl Runner {
     }
 }
 
+/// A sample of how many messages are waiting in a bounded channel.
+///
+/// If the queue of the [`Runner`] is full most of the time,
+/// the downloader is ahead of the solvers (i.e. solving is the bottleneck).
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash, Eq)]
+pub struct QueueDepth {
+    pub queued:   usize,
+    pub capacity: usize,
+}
+
+impl QueueDepth {
+    pub fn sample<T>(tx: &mpsc::Sender<T>) -> Self {
+        let capacity = tx.max_capacity();
+        let queued = capacity - tx.capacity();
+        Self { queued, capacity }
+    }
+
+    pub fn is_saturated(&self) -> bool {
+        self.queued >= self.capacity
+    }
+}
+
+impl fmt::Display for QueueDepth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{} queued", self.queued, self.capacity)
+    }
+}
+
+/// Publishes how many inputs are waiting in the queue of `tx` every `period`
+/// until all (strong) senders of the queue have been dropped.
+async fn sample_queue_depth<T>(
+    tx: mpsc::WeakSender<T>,
+    tx_queue: watch::Sender<QueueDepth>,
+    period: Duration,
+) {
+    let mut ticker = time::interval(period);
+    loop {
+        ticker.tick().await;
+
+        let Some(tx) = tx.upgrade() else {
+            return; // All inputs have been queued already
+        };
+
+        let depth = QueueDepth::sample(&tx);
+        tx_queue.send_if_modified(|old| {
+            let is_modified = *old != depth;
+            *old = depth;
+            is_modified
+        });
+    }
+}
+
 async fn run_actor(
     mut rx: mpsc::Receiver<(Solver, Parts, Input)>,
     tx: mpsc::Sender<Event>,
+    options: SolveOptions,
+    shutdown: watch::Receiver<bool>,
 ) {
-    while let Some((solver, parts, input)) = rx.recv().await {
+    // In single-thread mode, a dedicated pool runs one solver after another.
+    // Otherwise, we use rayon's global thread pool.
+    let pool = options.single_thread.then(|| {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .expect("Failed to create single-threaded pool");
+        Arc::new(pool)
+    });
+
+    while let Some((solver, parts, mut input)) = rx.recv().await {
+        if tx.is_closed() || *shutdown.borrow() {
+            return; // UI stopped early, e.g. because of `--fail-fast`
+        }
+
+        if options.normalize_input {
+            input = parser::normalize_input(&input).into_owned();
+        }
+
         let tx = tx.clone();
-        task::spawn(await_rayon_thread(move || {
-            solver.solve(parts, &input, tx)
-        }));
+        let shutdown = shutdown.clone();
+        let pool = pool.clone();
+        task::spawn(async move {
+            let tx_solver = tx.clone();
+            let result = await_rayon_thread(pool.as_deref(), move || {
+                solver.solve(parts, &input, tx_solver)
+            })
+            .await;
+
+            // Sending events fails if the UI stopped early.
+            // That's not an error of the solver, so ignore it.
+            if !tx.is_closed() && !*shutdown.borrow() {
+                result.expect("Failed to run solver thread");
+            }
+        });
     }
 }
 
-async fn await_rayon_thread<F>(f: F)
+/// Runs `f` on a thread of `pool`, or of rayon's global thread pool
+/// if `pool` is `None`, and waits for it to complete.
+async fn await_rayon_thread<F>(pool: Option<&ThreadPool>, f: F) -> Result<()>
 where
     F: (FnOnce() -> Result<()>) + Send + 'static,
 {
     let (tx, rx) = oneshot::channel();
 
-    rayon::spawn_fifo(|| {
+    let job = || {
         let result = f();
 
         // If the receiver (the async context of this function)
         // is suddenly gone, we're probably shutting down anyways,
         // so drop any error in that case.
         let _ = tx.send(result);
-    });
+    };
+
+    match pool {
+        Some(pool) => pool.spawn_fifo(job),
+        None => rayon::spawn_fifo(job),
+    }
 
     rx.await
         .expect("Failed to wait for solver thread")
-        .expect("Failed to run solver thread")
 }
 
 #[doc(hidden)]
@@ -121,9 +231,12 @@ where
     let p1 = || solve_part(y, d, Part::Part1, p1, tx);
     let p2 = || solve_part(y, d, Part::Part2, p2, tx);
 
+    // If there's only a single thread (e.g. in `--single-thread` mode),
+    // run part two strictly after part one to get reproducible timings.
     let (p1, p2) = match parts {
         Parts::First => (p1(), Ok(())),
         Parts::Second => (Ok(()), p2()),
+        Parts::Both if rayon::current_num_threads() == 1 => (p1(), p2()),
         Parts::Both => rayon::join(p1, p2),
     };
 
@@ -221,3 +334,80 @@ where
     tx.blocking_send(data)
         .or_wrap_with(|| "Failed to send data")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queue_depth() -> Result<()> {
+        let (tx, mut rx) = mpsc::channel(3);
+
+        let depth = QueueDepth::sample(&tx);
+        assert_eq!(depth.to_string(), "0/3 queued");
+        assert!(!depth.is_saturated());
+
+        for i in 0..3 {
+            tx.try_send(i).or_wrap()?;
+        }
+
+        let depth = QueueDepth::sample(&tx);
+        assert_eq!(depth.to_string(), "3/3 queued");
+        assert!(depth.is_saturated());
+
+        rx.try_recv().or_wrap()?;
+
+        let depth = QueueDepth::sample(&tx);
+        assert_eq!(depth, QueueDepth {
+            queued:   2,
+            capacity: 3,
+        });
+        assert!(!depth.is_saturated());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn sample_queue_depth_periodically() -> Result<()> {
+        let timeout = Duration::from_secs(10);
+        let (tx, mut rx) = mpsc::channel(3);
+        let (tx_queue, mut rx_queue) = watch::channel(QueueDepth::default());
+
+        let sampler = task::spawn(sample_queue_depth(
+            tx.downgrade(),
+            tx_queue,
+            Duration::from_millis(1),
+        ));
+
+        for i in 0..2 {
+            tx.send(i).await.or_wrap()?;
+        }
+
+        let queued_2 = rx_queue.wait_for(|depth| depth.queued == 2);
+        let depth = *time::timeout(timeout, queued_2)
+            .await
+            .or_wrap()?
+            .or_wrap()?;
+        assert_eq!(depth, QueueDepth {
+            queued:   2,
+            capacity: 3,
+        });
+
+        rx.recv().await;
+
+        let queued_1 = rx_queue.wait_for(|depth| depth.queued == 1);
+        time::timeout(timeout, queued_1)
+            .await
+            .or_wrap()?
+            .or_wrap()?;
+
+        drop(tx);
+
+        time::timeout(timeout, sampler)
+            .await
+            .or_wrap_with(|| "Sampler did not stop after queue was closed")?
+            .or_wrap()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,53 @@
+use lazy_errors::{prelude::*, Result};
+
+/// Applies `step` to `initial` repeatedly until the state stops changing.
+///
+/// Returns the stable state and the number of steps that changed the state,
+/// i.e. the number of steps it took to reach the stable state.
+/// Returns an error if the state is still changing after `max_iters` steps.
+#[allow(dead_code)]
+pub fn simulate_until_stable<S: Clone + Eq>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    max_iters: usize,
+) -> Result<(S, usize)> {
+    let mut state = initial;
+    for i in 0..max_iters {
+        let next = step(&state);
+        if next == state {
+            return Ok((state, i));
+        }
+
+        state = next;
+    }
+
+    Err(err!("State did not stabilize within {max_iters} steps"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn simulate_until_stable() -> Result<()> {
+        let halve = |n: &u32| n / 2;
+
+        let (state, steps) = super::simulate_until_stable(100, halve, 100)?;
+        assert_eq!(state, 0);
+        assert_eq!(steps, 7); // 50, 25, 12, 6, 3, 1, 0
+
+        let (state, steps) = super::simulate_until_stable(0, halve, 100)?;
+        assert_eq!(state, 0);
+        assert_eq!(steps, 0);
+
+        Ok(())
+    }
+
+    #[test]
+    fn simulate_until_stable_fails_when_exceeding_max_iters() {
+        let toggle = |b: &bool| !b;
+
+        let err = super::simulate_until_stable(true, toggle, 10).unwrap_err();
+        assert_eq!(err.to_string(), "State did not stabilize within 10 steps");
+    }
+}
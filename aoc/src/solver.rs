@@ -1,12 +1,14 @@
 use std::{
+    any::Any,
     fmt::{Debug, Display},
+    path::PathBuf,
     time::{Duration, Instant},
 };
 
 use lazy_errors::Result;
 use tokio::sync::mpsc;
 
-use crate::ident::{Day, Part, Year};
+use crate::ident::{Day, Id, Part, Year};
 
 /// Creates a [`Solver`] for a certain Advent of Code puzzle.
 ///
@@ -51,6 +53,25 @@ use crate::ident::{Day, Part, Year};
 ///
 /// let s = solver!(Y21, D01, y21d01p1, y21d01p2, y21d01_preproc);
 /// ```
+///
+/// Optionally, you can add tags to a solver, e.g. to describe the kind
+/// of puzzle it solves. Tags allow users to select puzzles by kind
+/// (see [`Solver::has_tag`]):
+///
+/// ```
+/// use aoc::{day::*, solver, year::*};
+///
+/// fn y21d01p1(_input: &str) -> Result<u8, String> {
+///     Ok(42)
+/// }
+///
+/// fn y21d01p2(_input: &str) -> Result<u8, String> {
+///     Ok(42 + 42)
+/// }
+///
+/// let s = solver!(Y21, D01, y21d01p1, y21d01p2, tags = ["grid"]);
+/// assert!(s.has_tag("grid"));
+/// ```
 // Implementation Notes:
 //
 // It may look like too many details leak into the public API of this module.
@@ -70,6 +91,29 @@ use crate::ident::{Day, Part, Year};
 //   parsing and solving).
 #[macro_export]
 macro_rules! solver {
+    (
+        $year:ident,
+        $day:ident,
+        $solver1:path,
+        $solver2:path,
+        tags = [$($tag:literal),* $(,)?]
+    ) => {
+        $crate::solver!($year, $day, $solver1, $solver2)
+            .with_tags(&[$($tag),*])
+    };
+
+    (
+        $year:ident,
+        $day:ident,
+        $solver1:path,
+        $solver2:path,
+        $parser:expr,
+        tags = [$($tag:literal),* $(,)?]
+    ) => {
+        $crate::solver!($year, $day, $solver1, $solver2, $parser)
+            .with_tags(&[$($tag),*])
+    };
+
     ($year:ident, $day:ident, $solver1:path, $solver2:path) => {{
         let runner: $crate::solver::RunnerFn = |parts, input, tx| {
             $crate::runner::skip_preproc($year, $day, &tx)?;
@@ -125,11 +169,15 @@ macro_rules! solver {
 /// (2) sends the puzzle answers through a channel for the UI to interpret.
 /// As long as closures don't capture variables, Rust allows them to be coerced
 /// into function pointers. The [`solver!`] macro does just that.
+// Comparing `runner` pointers is fine here: `year` and `day` come first
+// and are unique among all solvers anyway.
+#[allow(unknown_lints, unpredictable_function_pointer_comparisons)]
 #[derive(Debug, Clone, PartialEq, PartialOrd, Hash, Eq, Ord)]
 pub struct Solver {
     year:   Year,
     day:    Day,
     runner: RunnerFn,
+    tags:   &'static [&'static str],
 }
 
 /// Indicates whether to run only the first or only the second part
@@ -153,6 +201,57 @@ pub enum Parts {
     Both,
 }
 
+/// Selects the order of the per-puzzle lines printed after all puzzles
+/// were solved, such as the `--profile-parse` report.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash, Eq, clap::ValueEnum)]
+pub enum SummaryOrder {
+    /// The same order as the table, i.e. by year and day.
+    #[default]
+    Id,
+    /// Slowest puzzles first, by the total time of parsing and solving.
+    Time,
+}
+
+/// Options that change how the `solve` command runs the solvers.
+#[derive(Debug, Clone, Default, PartialEq, Hash, Eq)]
+pub struct SolveOptions {
+    /// Stop running solvers as soon as any step failed.
+    pub fail_fast: bool,
+
+    /// Remove carriage returns from puzzle inputs and make them end
+    /// with exactly one newline before passing them to the solvers.
+    pub normalize_input: bool,
+
+    /// Write each event of the actor pipeline to this file
+    /// as a line of JSON.
+    pub dump_events: Option<PathBuf>,
+
+    /// Never download puzzle inputs. Skip puzzles that aren't cached instead.
+    pub offline: bool,
+
+    /// After solving, print the share of time each puzzle spent parsing.
+    pub profile_parse: bool,
+
+    /// The order of the lines printed because of `profile_parse`.
+    pub sort_summary: SummaryOrder,
+
+    /// Read and save personal puzzle inputs in this directory
+    /// instead of in the cache directory.
+    pub input_dir: Option<PathBuf>,
+
+    /// If not empty, only run solvers that have at least one of these tags.
+    pub tags: Vec<String>,
+
+    /// Run all solvers on a single thread, one after another,
+    /// and run part two only after part one completed.
+    pub single_thread: bool,
+
+    /// Instead of showing the terminal user interface,
+    /// print the answers of the (single) selected puzzle
+    /// and solve it again each time its input changes.
+    pub watch: bool,
+}
+
 #[derive(Debug)]
 pub struct Event {
     pub year:  Year,
@@ -161,6 +260,12 @@ pub struct Event {
     pub state: State,
 }
 
+impl Event {
+    pub fn id(&self) -> Id<(Year, Day)> {
+        Id((self.year, self.day))
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Hash, Eq, Ord)]
 pub enum Step {
     Download,
@@ -169,11 +274,42 @@ pub enum Step {
     Part2,
 }
 
+impl Step {
+    /// Describes what this step does with puzzle `id`,
+    /// e.g. `download y24d01 input` or `solve y24d01p1`.
+    pub fn describe(self, id: Id<(Year, Day)>) -> String {
+        let Id((y, d)) = id;
+        match self {
+            Step::Download => format!("download {id} input"),
+            Step::Preproc => format!("preprocess {id} input"),
+            Step::Part1 => format!("solve {}", Id((y, d, Part::Part1))),
+            Step::Part2 => format!("solve {}", Id((y, d, Part::Part2))),
+        }
+    }
+}
+
+impl Display for Step {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Step::Download => write!(f, "download"),
+            Step::Preproc => write!(f, "prep"),
+            Step::Part1 => write!(f, "part 1"),
+            Step::Part2 => write!(f, "part 2"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum State {
     Waiting,
     Skipped,
+    /// The step could not run because the puzzle input is not available,
+    /// e.g. in offline mode. Contains the reason, e.g. `"offline"`.
+    Unavailable(&'static str),
     Started(Instant),
+    /// Like [`State::Started`], but also indicates the number of bytes
+    /// that have been processed so far (e.g. downloaded).
+    Progress(Instant, usize),
     Done(Duration, Result<Option<Box<dyn PuzzleAnswer>>>),
 }
 
@@ -181,9 +317,31 @@ pub enum State {
 /// This trait is basically an alias that allows
 /// solvers to return virtually any “regular” data type as answer,
 /// such as `usize` or `String`, and even custom structs.
-pub trait PuzzleAnswer: Display + Debug + Send + Sync + 'static {}
+pub trait PuzzleAnswer: Display + Debug + Send + Sync + 'static {
+    fn as_any(&self) -> &dyn Any;
+}
 
-impl<T> PuzzleAnswer for T where T: Display + Debug + Send + Sync + 'static {}
+impl<T> PuzzleAnswer for T
+where
+    T: Display + Debug + Send + Sync + 'static,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn PuzzleAnswer {
+    /// Returns the answer as its concrete type `T`, if it has that type,
+    /// so tests can check answers without comparing their string form.
+    ///
+    /// Prefer this over calling [`PuzzleAnswer::as_any`] on a
+    /// `Box<dyn PuzzleAnswer>` directly: the box itself is a
+    /// [`PuzzleAnswer`] too, so that would return the box, not its content.
+    #[allow(dead_code)]
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.as_any().downcast_ref()
+    }
+}
 
 #[doc(hidden)]
 pub type RunnerFn = fn(Parts, &str, mpsc::Sender<Event>) -> Result<()>;
@@ -200,7 +358,17 @@ impl From<Part> for Step {
 impl Solver {
     #[doc(hidden)]
     pub const fn new(year: Year, day: Day, runner: RunnerFn) -> Self {
-        Self { year, day, runner }
+        Self {
+            year,
+            day,
+            runner,
+            tags: &[],
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_tags(self, tags: &'static [&'static str]) -> Self {
+        Self { tags, ..self }
     }
 
     pub fn year(&self) -> Year {
@@ -211,6 +379,18 @@ impl Solver {
         self.day
     }
 
+    pub fn id(&self) -> Id<(Year, Day)> {
+        Id((self.year, self.day))
+    }
+
+    pub fn tags(&self) -> &'static [&'static str] {
+        self.tags
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.contains(&tag)
+    }
+
     /// Run the solver, i.e. preprocessing, part one, and part two,
     /// depending on the `parts` filter and whether a separate
     /// preprocessing stage exists for this solver.
@@ -230,6 +410,39 @@ impl Solver {
         let f = self.runner;
         f(parts, input, tx)
     }
+
+    /// Runs the solver like [`Solver::solve`] on the current thread
+    /// and returns all events it sent, in order.
+    ///
+    /// This allows calling a solver without setting up the actor system,
+    /// e.g. when embedding this crate as a library, in tests,
+    /// or to check the answers of the examples (`aoc test-examples`).
+    ///
+    /// Panics when called from within an asynchronous execution context.
+    pub fn run_blocking(
+        &self,
+        parts: Parts,
+        input: &str,
+    ) -> Result<Vec<Event>> {
+        let (tx, mut rx) = mpsc::channel(num_threads());
+
+        std::thread::scope(|scope| {
+            let collector = scope.spawn(move || {
+                let mut events = vec![];
+                while let Some(event) = rx.blocking_recv() {
+                    events.push(event);
+                }
+                events
+            });
+
+            let result = self.solve(parts, input, tx);
+            let events = collector
+                .join()
+                .unwrap_or_else(|e| std::panic::resume_unwind(e));
+
+            result.map(|()| events)
+        })
+    }
 }
 
 /// Returns the number of threads to use as returned from
@@ -242,3 +455,37 @@ pub fn num_threads() -> usize {
         .map(std::num::NonZeroUsize::get)
         .unwrap_or(1)
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use crate::ident::{day::*, year::*};
+
+    use super::*;
+
+    #[test_case(Step::Download, "download")]
+    #[test_case(Step::Preproc, "prep")]
+    #[test_case(Step::Part1, "part 1")]
+    #[test_case(Step::Part2, "part 2")]
+    fn step_display(step: Step, expected: &str) {
+        assert_eq!(step.to_string(), expected);
+    }
+
+    #[test_case(Step::Download, "download y24d01 input")]
+    #[test_case(Step::Preproc, "preprocess y24d01 input")]
+    #[test_case(Step::Part1, "solve y24d01p1")]
+    #[test_case(Step::Part2, "solve y24d01p2")]
+    fn step_describe(step: Step, expected: &str) {
+        assert_eq!(step.describe(Id((Y24, D01))), expected);
+    }
+
+    #[test]
+    fn downcast_answer() {
+        let answer: Box<dyn PuzzleAnswer> = Box::new(42_u64);
+
+        assert_eq!(answer.downcast_ref::<u64>(), Some(&42));
+        assert_eq!(answer.downcast_ref::<usize>(), None);
+        assert_eq!(answer.downcast_ref::<String>(), None);
+    }
+}
@@ -0,0 +1,240 @@
+use std::future::Future;
+
+use lazy_errors::{prelude::*, Result};
+use lazy_regex::regex_captures;
+
+use crate::{
+    fetcher,
+    fs::Config,
+    ident::{Day, Id, Part, Year},
+};
+
+/// How adventofcode.com responded to an answer we submitted.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Contains the time left to wait, if adventofcode.com told us.
+    TooRecent(Option<String>),
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Detects the [`Verdict`] in the HTML page adventofcode.com responds
+    /// with after an answer has been submitted.
+    pub fn from_html(html: &str) -> Result<Self> {
+        if html.contains("That's the right answer") {
+            return Ok(Verdict::Correct);
+        }
+
+        if html.contains("That's not the right answer") {
+            if html.contains("your answer is too high") {
+                return Ok(Verdict::TooHigh);
+            }
+            if html.contains("your answer is too low") {
+                return Ok(Verdict::TooLow);
+            }
+            return Ok(Verdict::Incorrect);
+        }
+
+        if html.contains("You gave an answer too recently") {
+            let wait = regex_captures!(r"You have (.+?) left to wait", html)
+                .map(|(_, wait)| wait.to_owned());
+            return Ok(Verdict::TooRecent(wait));
+        }
+
+        if html.contains("You don't seem to be solving the right level") {
+            return Ok(Verdict::AlreadySolved);
+        }
+
+        Err(err!("Unexpected response from adventofcode.com"))
+    }
+
+    pub fn is_correct(&self) -> bool {
+        *self == Verdict::Correct
+    }
+}
+
+impl std::fmt::Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::TooHigh => write!(f, "Wrong answer: Too high."),
+            Verdict::TooLow => write!(f, "Wrong answer: Too low."),
+            Verdict::Incorrect => write!(f, "Wrong answer."),
+            Verdict::TooRecent(None) => {
+                write!(f, "You gave an answer too recently. Please wait.")
+            }
+            Verdict::TooRecent(Some(wait)) => write!(
+                f,
+                "You gave an answer too recently. Please wait {wait}."
+            ),
+            Verdict::AlreadySolved => {
+                write!(f, "Puzzle not available or already solved.")
+            }
+        }
+    }
+}
+
+/// Submits `answer` as the answer to the puzzle part `id`.
+///
+/// Requires the session cookie to be saved (see `aoc login`).
+pub async fn submit(
+    config: &Config,
+    id: Id<(Year, Day, Part)>,
+    answer: &str,
+) -> Result<Verdict> {
+    submit_with(config, id, answer, post).await
+}
+
+async fn submit_with<F, Fut>(
+    config: &Config,
+    id: Id<(Year, Day, Part)>,
+    answer: &str,
+    post: F,
+) -> Result<Verdict>
+where
+    F: FnOnce(String, String, [(&'static str, String); 2]) -> Fut,
+    Fut: Future<Output = Result<String>>,
+{
+    let session_cookie = config.require_session_cookie()?;
+
+    let Id((_, _, part)) = id;
+    let level = u8::from(part).to_string();
+    let form = [("level", level), ("answer", answer.to_owned())];
+
+    let html = post(id.answer_url(), session_cookie, form).await?;
+    Verdict::from_html(&html)
+}
+
+async fn post(
+    url: String,
+    session_cookie: String,
+    form: [(&'static str, String); 2],
+) -> Result<String> {
+    fetcher::post(&url, &session_cookie, &form)
+        .await
+        .or_wrap()?
+        .text()
+        .await
+        .or_wrap_with(|| "Failed to receive response")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use test_case::test_case;
+
+    use crate::fs::{create_config_for, tempdir};
+
+    use super::*;
+
+    const CORRECT: &str = concat!(
+        "<article><p>That's the right answer! ",
+        "You are one gold star closer to saving your vacation.</p></article>"
+    );
+
+    const TOO_HIGH: &str = concat!(
+        "<article><p>That's not the right answer; ",
+        "your answer is too high. ",
+        "Please wait one minute before trying again.</p></article>"
+    );
+
+    const TOO_LOW: &str = concat!(
+        "<article><p>That's not the right answer; ",
+        "your answer is too low. ",
+        "Please wait one minute before trying again.</p></article>"
+    );
+
+    const INCORRECT: &str = concat!(
+        "<article><p>That's not the right answer. ",
+        "Please wait one minute before trying again.</p></article>"
+    );
+
+    const TOO_RECENT: &str = concat!(
+        "<article><p>You gave an answer too recently; ",
+        "you have to wait after submitting an answer ",
+        "before trying again.  You have 42s left to wait.</p></article>"
+    );
+
+    const WRONG_LEVEL: &str = concat!(
+        "<article><p>You don't seem to be solving the right level.  ",
+        "Did you already complete it?</p></article>"
+    );
+
+    #[test_case(CORRECT, Verdict::Correct; "correct")]
+    #[test_case(TOO_HIGH, Verdict::TooHigh; "too high")]
+    #[test_case(TOO_LOW, Verdict::TooLow; "too low")]
+    #[test_case(INCORRECT, Verdict::Incorrect; "incorrect")]
+    #[test_case(
+        TOO_RECENT,
+        Verdict::TooRecent(Some(String::from("42s")));
+        "too recent")]
+    #[test_case(WRONG_LEVEL, Verdict::AlreadySolved; "already solved")]
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn submit(html: &'static str, expected: Verdict) -> Result<()> {
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+        config.save_session_cookie("mock cookie")?;
+
+        let id = "y24d01p2".parse()?;
+        let verdict = submit_with(&config, id, "42", |url, cookie, form| {
+            assert_eq!(url, "https://adventofcode.com/2024/day/1/answer");
+            assert_eq!(cookie, "mock cookie");
+            assert_eq!(form, [
+                ("level", String::from("2")),
+                ("answer", String::from("42"))
+            ]);
+            async { Ok(String::from(html)) }
+        })
+        .await?;
+
+        assert_eq!(verdict, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn submit_fails_on_unexpected_response() -> Result<()> {
+        let tempdir = tempdir()?;
+        let mut config = create_config_for(&tempdir)?;
+        config.save_session_cookie("mock cookie")?;
+
+        let id = "y24d01p1".parse()?;
+        let err = submit_with(&config, id, "42", |_, _, _| async {
+            Ok(String::from("<html>Something else</html>"))
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Unexpected response from adventofcode.com"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[cfg_attr(miri, ignore)] // Because of `RepoDir`/`create_config_for`
+    async fn submit_fails_when_not_logged_in() -> Result<()> {
+        let tempdir = tempdir()?;
+        let config = create_config_for(&tempdir)?;
+
+        let posted = Cell::new(false);
+        let id = "y24d01p1".parse()?;
+        let err = submit_with(&config, id, "42", |_, _, _| {
+            posted.set(true);
+            async { Ok(String::from(CORRECT)) }
+        })
+        .await
+        .unwrap_err();
+
+        assert_eq!(err.to_string(), "Not logged in");
+        assert!(!posted.get());
+        Ok(())
+    }
+}
@@ -9,27 +9,45 @@ use lazy_errors::{prelude::*, Result};
 use ratatui::{
     crossterm::terminal::{disable_raw_mode, enable_raw_mode},
     prelude::*,
-    widgets::List,
+    widgets::{List, ListItem},
     TerminalOptions, Viewport,
 };
 use tokio::{
-    sync::mpsc,
+    sync::{mpsc, watch},
     task::{self, JoinHandle},
     time::{interval, Interval},
 };
 
 use crate::{
-    ident::{part, Day, Id, Year},
-    solver::{num_threads, Event, Parts, Solver, State, Step},
+    ident::{Day, Id, Part, Year},
+    solver::{
+        num_threads, Event, Parts, SolveOptions, Solver, State, Step,
+        SummaryOrder,
+    },
 };
 
-const TABLE_HEADER: &str = "\
-Day ───┬ Fetch ──┬ Parse ──┬ \
-Part 1 ──────────────────────┬ \
-Part 2 ─────────────────────
-";
-
-const SPINNERS: [&str; 8] = ["⢎⡡", "⢎⡑", "⢎⠱", "⠎⡱", "⢊⡱", "⢌⡱", "⢆⡱", "⢎⡰"];
+/// The minimum width of the answer in the columns of part 1 and part 2.
+/// The columns will be wider if there are longer answers.
+const W_ANSWER_MIN: usize = 20;
+
+const SPINNERS: &[&str] = &["⢎⡡", "⢎⡑", "⢎⠱", "⠎⡱", "⢊⡱", "⢌⡱", "⢆⡱", "⢎⡰"];
+
+/// Spinner frames for terminals that can't display braille characters.
+pub const ASCII_SPINNERS: &[&str] = &[" |", " /", " -", " \\"];
+
+// If and only if
+// (a) rendering is fast enough, and
+// (b) the program is not busy handling async events
+// the duration between ticks will be equal to the interval we specify here.
+// In all other cases, the number of elapsed milliseconds will increase by a
+// number larger than specified below. Nevertheless, to increase chances
+// that the least significant digit of the number of elapsed milliseconds
+// changes each tick, we use an interval with `interval % 10 != 0`.
+//
+// While the number of milliseconds should change rather fast on the
+// screen, the spinner should not appear frantic. The interval below
+// is a good tradeoff, IMO.
+const TICK_INTERVAL: Duration = Duration::from_millis(97);
 
 const ERR_TERM_IS_NONE: &str =
     "Failed to lookup terminal handle (internal error)";
@@ -48,13 +66,43 @@ type Terminal = ratatui::Terminal<CrosstermBackend<Stdout>>;
 /// in that case the thread will panic if [`UiActor::close`] returns an error.
 struct UiActor {
     term:   Option<Terminal>, // Never `None` except usually in `drop()`
+    config: UiConfig,
     ticks:  usize,
     states: Vec<PuzzleState>,
 }
 
 pub struct Ui {
-    tx:   mpsc::Sender<Event>,
-    join: JoinHandle<Result<Summary, Terminated>>,
+    tx: mpsc::Sender<Event>,
+    shutdown: watch::Receiver<bool>,
+    join: JoinHandle<Result<RunReport, Terminated>>,
+}
+
+/// Configures the look and the refresh rate of the [`Ui`].
+///
+/// The [`Default`] config uses braille characters for the spinner.
+/// Each spinner frame should be two characters wide.
+#[derive(Debug, Clone, PartialEq, Hash, Eq)]
+pub struct UiConfig {
+    pub spinners: &'static [&'static str],
+    pub tick_interval: Duration,
+    pub theme: Theme,
+    /// If set, longer answers will be truncated in the answer columns.
+    pub max_answer_width: Option<usize>,
+    /// If set, the answer columns will be exactly this wide,
+    /// regardless of the answers. Takes precedence over `max_answer_width`.
+    pub fixed_answer_width: Option<usize>,
+}
+
+/// Selects the colors of the [`Ui`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash, Eq, clap::ValueEnum)]
+pub enum Theme {
+    /// Colors that are easy to read on dark terminal backgrounds.
+    Dark,
+    /// Colors that are easy to read on light terminal backgrounds.
+    Light,
+    /// No colors at all.
+    #[default]
+    Mono,
 }
 
 #[derive(Debug)]
@@ -67,18 +115,108 @@ pub enum Action {
 /// Basically a “row” on the TUI screen.
 #[derive(Debug)]
 struct PuzzleState {
-    y:  Year,
-    d:  Day,
+    id: Id<(Year, Day)>,
     pd: State,
     p0: State,
     p1: State,
     p2: State,
 }
 
+impl PuzzleState {
+    /// Returns how much of the total time of parsing and solving
+    /// was spent parsing, in percent.
+    ///
+    /// Returns `None` if parsing did not succeed
+    /// or if none of the parts was solved.
+    fn parse_time_percentage(&self) -> Option<f64> {
+        let State::Done(parse, Ok(_)) = &self.p0 else {
+            return None;
+        };
+
+        let solve: Vec<Duration> = [&self.p1, &self.p2]
+            .into_iter()
+            .filter_map(|state| match state {
+                State::Done(t, _) => Some(*t),
+                _ => None,
+            })
+            .collect();
+
+        if solve.is_empty() {
+            return None;
+        }
+
+        let total = *parse + solve.iter().sum::<Duration>();
+        if total.is_zero() {
+            return None;
+        }
+
+        Some(100.0 * parse.as_secs_f64() / total.as_secs_f64())
+    }
+
+    /// Returns the total time spent parsing and solving so far,
+    /// counting only steps that are done (successfully or not).
+    fn total_time(&self) -> Duration {
+        [&self.p0, &self.p1, &self.p2]
+            .into_iter()
+            .filter_map(|state| match state {
+                State::Done(t, _) => Some(*t),
+                _ => None,
+            })
+            .sum()
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, PartialOrd, Hash, Eq, Ord)]
 pub enum Summary {
     Success,
     SomeRunnersFailed,
+    /// The filter did not match any puzzle (or, when watching,
+    /// more than one), so nothing was run.
+    NoPuzzlesMatched,
+}
+
+/// Counts what happened while the [`Ui`] was running.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Hash, Eq)]
+pub struct RunReport {
+    /// How many puzzle parts were solved.
+    pub solved: usize,
+    /// How many steps failed (downloads, parsing, or puzzle parts).
+    pub failed: usize,
+    /// How many personal puzzle inputs did not need to be downloaded.
+    pub skipped_downloads: usize,
+}
+
+impl RunReport {
+    /// Counts `event` if it is a final (skipped or done) state.
+    pub fn record(&mut self, event: &Event) {
+        match (event.step, &event.state) {
+            (Step::Download, State::Skipped) => self.skipped_downloads += 1,
+            (_, State::Done(_, Err(_))) => self.failed += 1,
+            (Step::Part1 | Step::Part2, State::Done(_, Ok(_))) => {
+                self.solved += 1
+            }
+            _ => (),
+        }
+    }
+
+    pub fn summary(&self) -> Summary {
+        if self.failed > 0 {
+            Summary::SomeRunnersFailed
+        } else {
+            Summary::Success
+        }
+    }
+}
+
+impl std::fmt::Display for RunReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} solved, {} failed", self.solved, self.failed)?;
+        match self.skipped_downloads {
+            0 => Ok(()),
+            1 => write!(f, ", 1 download skipped"),
+            n => write!(f, ", {n} downloads skipped"),
+        }
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -90,23 +228,84 @@ pub enum Terminated {
     InternalError(#[from] Error),
 }
 
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            spinners: SPINNERS,
+            tick_interval: TICK_INTERVAL,
+            theme: Theme::default(),
+            max_answer_width: None,
+            fixed_answer_width: None,
+        }
+    }
+}
+
+impl UiConfig {
+    /// Returns the spinner frame to display after `ticks` ticks.
+    fn spinner(&self, ticks: usize) -> &'static str {
+        match self.spinners.len() {
+            0 => "  ",
+            n => self.spinners[ticks % n],
+        }
+    }
+}
+
+impl Theme {
+    /// Returns the style of the column of `step`,
+    /// or of the error message of `step` if `is_error` is `true`.
+    pub fn style(self, step: Step, is_error: bool) -> Style {
+        let color = match (self, step, is_error) {
+            (Theme::Mono, _, _) => return Style::default(),
+            (Theme::Dark, _, true) => Color::LightRed,
+            (Theme::Dark, Step::Download | Step::Preproc, _) => Color::Gray,
+            (Theme::Dark, Step::Part1 | Step::Part2, _) => Color::LightGreen,
+            (Theme::Light, _, true) => Color::Red,
+            (Theme::Light, Step::Download | Step::Preproc, _) => {
+                Color::DarkGray
+            }
+            (Theme::Light, Step::Part1 | Step::Part2, _) => Color::Blue,
+        };
+
+        Style::default().fg(color)
+    }
+}
+
 impl Ui {
-    pub fn open(puzzles: Vec<(Solver, Parts)>) -> Result<Self> {
+    pub fn open(
+        puzzles: Vec<(Solver, Parts)>,
+        options: &SolveOptions,
+        config: UiConfig,
+    ) -> Result<Self> {
         // Even if event processing and screen rendering takes a lot of time,
         // it shouldn't block the executor tasks. Otherwise the puzzle solver
         // may be “blocked” (async wait) trying to send a `started(time_now)`
         // message while its timer is already running.
         // The execution time we measured would be incorrect in this case.
         let (tx, rx) = mpsc::channel(2 * num_threads());
-        let join = task::spawn(init_and_run(puzzles, rx));
-        Ok(Self { tx, join })
+        let (tx_shutdown, shutdown) = watch::channel(false);
+        let options = options.clone();
+        let join = task::spawn(init_and_run(
+            puzzles,
+            options,
+            config,
+            rx,
+            tx_shutdown,
+        ));
+        Ok(Self { tx, shutdown, join })
     }
 
     pub fn tx(&self) -> mpsc::Sender<Event> {
         self.tx.clone()
     }
 
-    pub async fn join(self) -> Result<Summary, Terminated> {
+    /// Returns a receiver that will be set to `true` as soon as the UI stops,
+    /// e.g. because the user pressed Ctrl-C.
+    /// Other actors should stop starting new work at that point.
+    pub fn shutdown(&self) -> watch::Receiver<bool> {
+        self.shutdown.clone()
+    }
+
+    pub async fn join(self) -> Result<RunReport, Terminated> {
         // Allow actor to shut down gracefully.
         drop(self.tx);
 
@@ -131,7 +330,7 @@ impl UiActor {
     /// this method will return an error if called a second time
     /// before calling [`UiActor::close`] on the value returned by
     /// the first call to [`UiActor::open`].
-    pub fn open(puzzles: &[(Solver, Parts)]) -> Result<Self> {
+    pub fn open(puzzles: &[(Solver, Parts)], config: UiConfig) -> Result<Self> {
         let mut is_open = UiActor::is_open()?;
         if *is_open {
             return Err(err!("TUI is already open"));
@@ -149,8 +348,7 @@ impl UiActor {
                 };
 
                 PuzzleState {
-                    y: solver.year(),
-                    d: solver.day(),
+                    id: solver.id(),
                     pd: State::Waiting,
                     p0: State::Waiting,
                     p1,
@@ -163,6 +361,7 @@ impl UiActor {
 
         Ok(UiActor {
             term,
+            config,
             states: puzzles,
             ticks: 0,
         })
@@ -176,33 +375,18 @@ impl UiActor {
     }
 
     fn update(&mut self, event: Event) -> Result<()> {
-        let Event {
-            year: y,
-            day: d,
-            step,
-            state,
-        } = event;
-        if let State::Done(_, Err(err)) = &state {
+        let id = event.id();
+        let Event { step, state, .. } = event;
+        if let Some(message) = format_message(id, step, &state) {
+            let is_error = matches!(state, State::Done(_, Err(_)));
+            let style = self.config.theme.style(step, is_error);
             // TODO: Use `insert_after` when something like that exists
             self.term
                 .as_mut()
                 .ok_or_else(|| Error::from_message(ERR_TERM_IS_NONE))?
                 .insert_before(1, |buf| {
-                    let action = match step {
-                        Step::Download => {
-                            format!("download {} input", Id((y, d)))
-                        }
-                        Step::Preproc => {
-                            format!("preprocess {} input", Id((y, d)))
-                        }
-                        Step::Part1 => {
-                            format!("solve {}", Id((y, d, part::P1)))
-                        }
-                        Step::Part2 => {
-                            format!("solve {}", Id((y, d, part::P2)))
-                        }
-                    };
-                    Line::from(format!("ERROR: Failed to {action}: {err}"))
+                    Line::from(message)
+                        .style(style)
                         .render(buf.area, buf);
                 })
                 .or_wrap_with(|| "Failed to display completed step")?;
@@ -211,8 +395,8 @@ impl UiActor {
         let record = self
             .states
             .iter_mut()
-            .find(|p| p.y == y && p.d == d)
-            .ok_or_else(|| err!("Failed to find puzzle {}", Id((y, d))))?;
+            .find(|p| p.id == id)
+            .ok_or_else(|| err!("Failed to find puzzle {id}"))?;
 
         match step {
             Step::Download => record.pd = state,
@@ -228,32 +412,78 @@ impl UiActor {
         self.ticks += 1;
     }
 
+    /// Returns a line for each answer that had to be truncated
+    /// to fit into its column, showing the full answer.
+    fn truncated_answers_report(&self) -> Vec<String> {
+        let widths = compute_answer_widths(
+            &self.states,
+            self.config.max_answer_width,
+            self.config.fixed_answer_width,
+        );
+        format_truncated_answers(&self.states, widths)
+    }
+
+    /// Returns a line for each puzzle that was parsed and solved,
+    /// showing the share of time spent parsing.
+    fn parse_time_report(&self, order: SummaryOrder) -> Vec<String> {
+        sort_states(&self.states, order)
+            .into_iter()
+            .filter_map(|state| {
+                let id = state.id;
+                let pct = state.parse_time_percentage()?;
+                Some(format!("{id}: {pct:5.1} % of time spent parsing"))
+            })
+            .collect()
+    }
+
     fn render(&mut self) -> Result<()> {
         self.term
             .as_mut()
             .ok_or_else(|| Error::from_message(ERR_TERM_IS_NONE))?
             .draw(|frame| {
                 let now = Instant::now();
-                let spinner = SPINNERS[self.ticks % SPINNERS.len()];
-
-                let mut lines: Vec<String> = vec![];
+                let spinner = self.config.spinner(self.ticks);
+                let theme = self.config.theme;
+                let styled = |text: String, step: Step, state: &State| {
+                    let is_error = matches!(state, State::Done(_, Err(_)));
+                    Span::styled(text, theme.style(step, is_error))
+                };
 
-                lines.push(TABLE_HEADER.to_string());
-                for PuzzleState {
-                    y,
-                    d,
-                    pd,
-                    p0,
-                    p1,
-                    p2,
-                } in self.states.iter()
-                {
-                    let id = Id((*y, *d));
-                    let dl = format_column_time(pd, now);
-                    let p0 = format_column_time(p0, now);
-                    let p1 = format_column_answer_and_time(p1, spinner, now);
-                    let p2 = format_column_answer_and_time(p2, spinner, now);
-                    lines.push(format!("{id} │ {dl} │ {p0} │ {p1} │ {p2}"));
+                let widths = compute_answer_widths(
+                    &self.states,
+                    self.config.max_answer_width,
+                    self.config.fixed_answer_width,
+                );
+                let [w1, w2] = widths;
+
+                let mut lines: Vec<ListItem> = vec![];
+
+                lines.push(ListItem::new(format_table_header(widths)));
+                for PuzzleState { id, pd, p0, p1, p2 } in self.states.iter() {
+                    let dl =
+                        styled(format_column_time(pd, now), Step::Download, pd);
+                    let p0 =
+                        styled(format_column_time(p0, now), Step::Preproc, p0);
+                    let p1 = styled(
+                        format_column_answer_and_time(p1, spinner, now, w1),
+                        Step::Part1,
+                        p1,
+                    );
+                    let p2 = styled(
+                        format_column_answer_and_time(p2, spinner, now, w2),
+                        Step::Part2,
+                        p2,
+                    );
+                    lines.push(ListItem::new(Line::from(vec![
+                        Span::raw(format!("{id} │ ")),
+                        dl,
+                        Span::raw(" │ "),
+                        p0,
+                        Span::raw(" │ "),
+                        p1,
+                        Span::raw(" │ "),
+                        p2,
+                    ])));
                 }
 
                 let lines = List::new(lines);
@@ -294,6 +524,75 @@ impl UiActor {
     }
 }
 
+/// The parts of [`UiActor`] that [`run_loop`] needs,
+/// so that the loop can be tested without a terminal.
+trait Screen {
+    fn update(&mut self, event: Event) -> Result<()>;
+    fn tick(&mut self);
+    fn render(&mut self) -> Result<()>;
+    fn resize(&mut self) -> Result<()>;
+}
+
+impl Screen for UiActor {
+    fn update(&mut self, event: Event) -> Result<()> {
+        UiActor::update(self, event)
+    }
+
+    fn tick(&mut self) {
+        UiActor::tick(self)
+    }
+
+    fn render(&mut self) -> Result<()> {
+        UiActor::render(self)
+    }
+
+    fn resize(&mut self) -> Result<()> {
+        UiActor::resize(self)
+    }
+}
+
+/// Remembers which events the [`run_loop`] passed on.
+#[cfg(test)]
+#[derive(Default)]
+pub struct MockScreen {
+    pub updates: Vec<(Day, Step)>,
+}
+
+#[cfg(test)]
+impl Screen for MockScreen {
+    fn update(&mut self, event: Event) -> Result<()> {
+        self.updates
+            .push((event.day, event.step));
+        Ok(())
+    }
+
+    fn tick(&mut self) {}
+
+    fn render(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn resize(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Like [`Ui::join`], but runs the [`run_loop`] on a [`MockScreen`]
+/// instead of the terminal. Returns as soon as the loop stops,
+/// e.g. after all senders of `rx` have been dropped.
+#[cfg(test)]
+pub async fn run_mock_loop(
+    rx: mpsc::Receiver<Event>,
+    options: &SolveOptions,
+) -> (Result<RunReport, Terminated>, MockScreen) {
+    let (_tx_action, rx_action) = mpsc::channel(1);
+    let ticker = ticker(Duration::from_secs(3600));
+    let mut screen = MockScreen::default();
+    let result = run_loop(rx, rx_action, ticker, options, &mut screen).await;
+
+    (result, screen)
+}
+
 impl Drop for UiActor {
     fn drop(&mut self) {
         take_mut::take_or_recover(
@@ -317,20 +616,41 @@ impl Drop for UiActor {
 
 async fn init_and_run(
     puzzles: Vec<(Solver, Parts)>,
+    options: SolveOptions,
+    config: UiConfig,
     rx: mpsc::Receiver<Event>,
-) -> Result<Summary, Terminated> {
+    tx_shutdown: watch::Sender<bool>,
+) -> Result<RunReport, Terminated> {
     // WARNING! The terminal MUST be set up before trying to read key presses.
     // In other words, `UiActor::open` MUST have completed
     // BEFORE `relay_user_actions` is spawned.
     // Otherwise something sometimes locks up until a key is pressed.
 
-    let mut ui = UiActor::open(&puzzles)?;
+    let ticker = ticker(config.tick_interval);
+    let mut ui = UiActor::open(&puzzles, config)?;
 
     let (tx_action, rx_action) = mpsc::channel(1);
     task::spawn(relay_user_actions(tx_action));
 
-    let result = run_loop(rx, rx_action, ticker(), &mut ui).await;
+    let result = run_loop(rx, rx_action, ticker, &options, &mut ui).await;
+
+    // Nobody is interested in events anymore; stop all other actors.
+    tx_shutdown.send_replace(true);
+
+    // Collect the report now but print it only after the TUI is closed.
+    let report = if options.profile_parse {
+        ui.parse_time_report(options.sort_summary)
+    } else {
+        vec![]
+    };
+    let answers = ui.truncated_answers_report();
+
     ui.close()?;
+
+    for line in answers.into_iter().chain(report) {
+        println!("{line}");
+    }
+
     result
 }
 
@@ -338,9 +658,10 @@ async fn run_loop(
     mut rx_event: mpsc::Receiver<Event>,
     mut rx_action: mpsc::Receiver<Action>,
     mut ticker: Interval,
-    ui: &mut UiActor,
-) -> Result<Summary, Terminated> {
-    let mut some_runners_failed = false;
+    options: &SolveOptions,
+    ui: &mut impl Screen,
+) -> Result<RunReport, Terminated> {
+    let mut report = RunReport::default();
     loop {
         tokio::select! {
             event_maybe = rx_event.recv() => {
@@ -348,11 +669,17 @@ async fn run_loop(
                     break;
                 };
 
-                if matches!(event.state, State::Done(_, Err(_))) {
-                    some_runners_failed = true;
-                }
+                let is_failure = is_failure(&event);
+                report.record(&event);
 
                 ui.update(event)?;
+
+                if is_failure && options.fail_fast {
+                    // Downloader and runner will stop starting new work
+                    // as soon as they notice they can't send events anymore.
+                    rx_event.close();
+                    break;
+                }
             },
             Some(action) = rx_action.recv() => {
                 match action {
@@ -378,13 +705,13 @@ async fn run_loop(
     // Render the final screen.
     ui.render()?;
 
-    if some_runners_failed {
-        // TODO: Pretty-print (hierarchy w/ backtrace) errors in detail here.
-        // TODO: Probably remove the on-demand `insert_before` hack afterwards.
-        Ok(Summary::SomeRunnersFailed)
-    } else {
-        Ok(Summary::Success)
-    }
+    // TODO: Pretty-print (hierarchy w/ backtrace) errors in detail here.
+    // TODO: Probably remove the on-demand `insert_before` hack afterwards.
+    Ok(report)
+}
+
+fn is_failure(event: &Event) -> bool {
+    matches!(event.state, State::Done(_, Err(_)))
 }
 
 async fn relay_user_actions(tx: mpsc::Sender<Action>) -> Result<()> {
@@ -421,20 +748,8 @@ async fn relay_user_actions(tx: mpsc::Sender<Action>) -> Result<()> {
     Ok(())
 }
 
-fn ticker() -> Interval {
-    // If and only if
-    // (a) rendering is fast enough, and
-    // (b) the program is not busy handling async events
-    // the duration between ticks will be equal to the interval we specify here.
-    // In all other cases, the number of elapsed milliseconds will increase by a
-    // number larger than specified below. Nevertheless, to increase chances
-    // that the least significant digit of the number of elapsed milliseconds
-    // changes each tick, we use an interval with `interval % 10 != 0`.
-    //
-    // While the number of milliseconds should change rather fast on the
-    // screen, the spinner should not appear frantic. The interval below
-    // is a good tradeoff, IMO.
-    let mut interval = interval(Duration::from_millis(97));
+fn ticker(period: Duration) -> Interval {
+    let mut interval = interval(period);
     interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
     interval
 }
@@ -506,48 +821,185 @@ fn restore_terminal(mut term: Terminal) -> Result<()> {
     errs.into()
 }
 
+/// Returns the width of the answers in the columns of part 1 and part 2,
+/// i.e. the length of the longest answer, but at least [`W_ANSWER_MIN`]
+/// and at most `max` (if set). If `fixed` is set, returns that width instead.
+fn compute_answer_widths(
+    states: &[PuzzleState],
+    max: Option<usize>,
+    fixed: Option<usize>,
+) -> [usize; 2] {
+    if let Some(fixed) = fixed {
+        return [fixed; 2];
+    }
+
+    let width = |state: &State| match state {
+        State::Done(_, Ok(Some(answer))) => answer.to_string().chars().count(),
+        _ => 0,
+    };
+
+    states
+        .iter()
+        .fold([W_ANSWER_MIN; 2], |[w1, w2], state| {
+            [w1.max(width(&state.p1)), w2.max(width(&state.p2))]
+        })
+        .map(|w| max.map_or(w, |max| w.min(max)))
+}
+
+/// Returns references to `states` in the given `order`.
+fn sort_states(
+    states: &[PuzzleState],
+    order: SummaryOrder,
+) -> Vec<&PuzzleState> {
+    let mut states: Vec<&PuzzleState> = states.iter().collect();
+    if order == SummaryOrder::Time {
+        states.sort_by_key(|state| std::cmp::Reverse(state.total_time()));
+    }
+    states
+}
+
+/// Returns a line containing the full answer for each answer
+/// that is wider than its column.
+fn format_truncated_answers(
+    states: &[PuzzleState],
+    widths: [usize; 2],
+) -> Vec<String> {
+    states
+        .iter()
+        .flat_map(|state| {
+            let parts = [(Part::Part1, &state.p1), (Part::Part2, &state.p2)];
+            parts
+                .into_iter()
+                .zip(widths)
+                .filter_map(move |((p, s), width)| {
+                    let State::Done(_, Ok(Some(answer))) = s else {
+                        return None;
+                    };
+                    let answer = answer.to_string();
+                    if answer.chars().count() <= width {
+                        return None;
+                    }
+                    let Id((y, d)) = state.id;
+                    let id = Id((y, d, p));
+                    Some(format!("{id}: {answer}"))
+                })
+        })
+        .collect()
+}
+
+/// Returns the table header matching answer columns of the given `widths`.
+fn format_table_header(widths: [usize; 2]) -> String {
+    // Each answer column also contains a space and the time (7 chars).
+    // The first column's header also spans the space before the separator.
+    let [w1, w2] = widths.map(|w| w + 1 + 7);
+    let w1 = w1 + 1;
+    format!(
+        "Day ───┬ Fetch ──┬ Parse ──┬ {:─<w1$}┬ {:─<w2$}",
+        "Part 1 ", "Part 2 "
+    )
+}
+
+/// Returns the line to print above the table when `step` of puzzle `id`
+/// reached `state`, if that state is worth a line of its own.
+fn format_message(
+    id: Id<(Year, Day)>,
+    step: Step,
+    state: &State,
+) -> Option<String> {
+    match state {
+        State::Done(_, Err(err)) => {
+            Some(format!("ERROR: Failed to {}: {err}", step.describe(id)))
+        }
+        State::Unavailable(reason) => {
+            Some(format!("NOTE: {id} skipped: {reason}"))
+        }
+        _ => None,
+    }
+}
+
 fn format_column_time(state: &State, now: Instant) -> String {
     match state {
         State::Waiting => "       ".to_string(),
-        State::Skipped => "    ---".to_string(),
+        State::Skipped | State::Unavailable(_) => "    ---".to_string(),
         State::Started(t) => format_time(&now.duration_since(*t)).to_string(),
+        State::Progress(_t, bytes) => format_bytes(*bytes),
         State::Done(t, Ok(_)) => format_time(t).to_string(),
         State::Done(_t, Err(_)) => " ERROR!".to_string(),
     }
 }
 
+/// Formats the answer (right-aligned to `width`) followed by the time.
 fn format_column_answer_and_time(
     state: &State,
     spinner: &str,
     now: Instant,
+    width: usize,
 ) -> String {
+    // The time is always 7 characters wide, "ERROR: " as well.
+    let w_total = width + 1 + 7;
+    let w_err = w_total - 7;
     match state {
-        //                      12345678901234567890123
-        State::Waiting => "                            ".to_string(),
-        State::Skipped => "                         ---".to_string(),
-        State::Started(t) => {
+        State::Waiting => format!("{:w_total$}", ""),
+        State::Skipped | State::Unavailable(_) => {
+            format!("{:>w_total$}", "---")
+        }
+        State::Started(t) | State::Progress(t, _) => {
             let time = format_time(&now.duration_since(*t));
-            format!("{spinner:>20} {time}") // spinner is double-width
+            format!("{spinner:>width$} {time}") // spinner is double-width
         }
         State::Done(t, Ok(None)) => {
             let time = format_time(t);
-            format!("{time:>28}")
+            format!("{time:>w_total$}")
         }
         State::Done(t, Ok(Some(result))) => {
             let time = format_time(t);
-            format!("{result:>20} {time}")
+            let result = truncate_with_ellipsis(&result.to_string(), width);
+            format!("{result:>width$} {time}")
         }
         State::Done(_t, Err(e)) => {
             let mut e = e.to_string();
-            if e.len() > 21 {
-                e.truncate(20);
+            if e.len() > w_err {
+                e.truncate(w_err - 1);
                 e.push('…');
             }
-            format!("ERROR: {:21}", &e[0..e.len()])
+            format!("ERROR: {:w_err$}", &e[0..e.len()])
         }
     }
 }
 
+/// Pads `answer` to `width` for a plain (non-TUI) renderer:
+/// Numeric answers are right-aligned, all others (such as the
+/// comma-separated lists of y24d17) are left-aligned for readability.
+///
+/// The TUI keeps right-aligning all answers next to their times.
+#[allow(dead_code)]
+fn align_answer_plain(answer: &str, width: usize) -> String {
+    let answer = truncate_with_ellipsis(answer, width);
+    if answer
+        .chars()
+        .all(|c| c.is_ascii_digit())
+    {
+        format!("{answer:>width$}")
+    } else {
+        format!("{answer:<width$}")
+    }
+}
+
+/// Shortens `text` to at most `width` characters (not bytes),
+/// ending with `…` if anything had to be cut off.
+fn truncate_with_ellipsis(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_owned();
+    }
+
+    let mut text: String = text
+        .chars()
+        .take(width.saturating_sub(1))
+        .collect();
+    text.push('…');
+    text
+}
+
 fn format_time(duration: &Duration) -> String {
     for (factor, symbol) in [(1, "µs"), (1_000, "ms")] {
         if duration < &Duration::from_micros(10_000 * factor) {
@@ -571,12 +1023,137 @@ fn format_time(duration: &Duration) -> String {
     String::from("  🧙   ")
 }
 
+/// Formats `bytes` with at most four digits, using decimal units
+/// so that each unit starts where the previous one ran out of digits.
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 10_000 {
+        return format!("{bytes:>4} B ");
+    }
+
+    let kb = bytes / 1000;
+    if kb < 10_000 {
+        return format!("{kb:>4} KB");
+    }
+
+    let mb = kb / 1000;
+    format!("{mb:>4} MB")
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
 
+    use crate::ident::{day::*, year::*};
+
+    use crate::solver::PuzzleAnswer;
+
     use super::*;
 
+    fn event(d: Day, step: Step, state: State) -> Event {
+        Event {
+            year: Y21,
+            day: d,
+            step,
+            state,
+        }
+    }
+
+    fn done(result: Result<Option<usize>>) -> State {
+        let result = result.map(|answer| {
+            answer.map(|answer| Box::new(answer) as Box<dyn PuzzleAnswer>)
+        });
+        State::Done(Duration::from_millis(1), result)
+    }
+
+    /// Returns a row of the table for y24 day `d` whose input was cached.
+    fn puzzle_state(d: Day, p0: State, p1: State, p2: State) -> PuzzleState {
+        PuzzleState {
+            id: Id((Y24, d)),
+            pd: State::Skipped,
+            p0,
+            p1,
+            p2,
+        }
+    }
+
+    /// Returns the state of a puzzle part that found `answer` in 42 ms.
+    fn done_answer(answer: &str) -> State {
+        let t = Duration::from_millis(42);
+        State::Done(t, Ok(Some(Box::new(answer.to_owned()))))
+    }
+
+    /// Sends `events` to a [`run_loop`] that renders to a [`MockScreen`].
+    /// The loop will not stop after the last event
+    /// unless all clones of `tx` have been dropped.
+    async fn send_to_mock_loop(
+        tx: mpsc::Sender<Event>,
+        rx: mpsc::Receiver<Event>,
+        events: Vec<Event>,
+        options: &SolveOptions,
+    ) -> (Result<RunReport, Terminated>, MockScreen) {
+        for event in events {
+            tx.send(event).await.unwrap();
+        }
+        drop(tx);
+
+        run_mock_loop(rx, options).await
+    }
+
+    #[tokio::test]
+    async fn run_loop_stops_on_failure_if_fail_fast() {
+        let events = vec![
+            event(D01, Step::Download, State::Skipped),
+            event(D01, Step::Preproc, done(Ok(None))),
+            event(D01, Step::Part1, done(Err(err!("Mock error")))),
+            event(D02, Step::Preproc, done(Ok(None))),
+            event(D02, Step::Part1, done(Ok(Some(42)))),
+        ];
+        let options = SolveOptions {
+            fail_fast: true,
+            ..SolveOptions::default()
+        };
+
+        // Keep a sender, so the loop can only stop because of `fail_fast`.
+        let (tx, rx) = mpsc::channel(10);
+        let run = send_to_mock_loop(tx.clone(), rx, events, &options);
+        let (result, screen) =
+            tokio::time::timeout(Duration::from_secs(10), run)
+                .await
+                .expect("run_loop() did not stop");
+        let report = result.unwrap();
+
+        assert_eq!(report.summary(), Summary::SomeRunnersFailed);
+        assert_eq!(report.failed, 1);
+        assert_eq!(report.solved, 0);
+        assert_eq!(screen.updates, vec![
+            (D01, Step::Download),
+            (D01, Step::Preproc),
+            (D01, Step::Part1),
+        ]);
+
+        // Actors stop because they can't send events anymore.
+        let event = event(D03, Step::Download, State::Skipped);
+        assert!(tx.send(event).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn run_loop_continues_on_failure_by_default() {
+        let events = vec![
+            event(D01, Step::Part1, done(Err(err!("Mock error")))),
+            event(D02, Step::Preproc, done(Ok(None))),
+            event(D02, Step::Part1, done(Ok(Some(42)))),
+        ];
+
+        let (tx, rx) = mpsc::channel(10);
+        let (result, screen) =
+            send_to_mock_loop(tx, rx, events, &SolveOptions::default()).await;
+        let report = result.unwrap();
+
+        assert_eq!(report.summary(), Summary::SomeRunnersFailed);
+        assert_eq!(screen.updates.len(), 3);
+        assert_eq!(report.solved, 1);
+    }
+
     //           12345678901234567890123
     #[test_case("                            ", |_, _| State::Waiting)]
     #[test_case("                         ---", |_, _| State::Skipped)]
@@ -596,10 +1173,293 @@ mod tests {
         let dur = Duration::from_millis(42);
         let begin = Instant::now() - dur;
         let state = state(begin, dur);
-        let actual = super::format_column_answer_and_time(&state, "⢎⡡", begin);
+        let actual = super::format_column_answer_and_time(
+            &state,
+            "⢎⡡",
+            begin,
+            W_ANSWER_MIN,
+        );
+        assert_eq!(&actual, expected);
+    }
+
+    #[test_case(State::Skipped, None; "skipped")]
+    #[test_case(
+        State::Unavailable("offline"),
+        Some("NOTE: y24d01 skipped: offline");
+        "unavailable")]
+    #[test_case(
+        State::Done(Duration::ZERO, Err(err!("Mock error"))),
+        Some("ERROR: Failed to download y24d01 input: Mock error");
+        "failed")]
+    fn format_message(state: State, expected: Option<&str>) {
+        let id = Id((Y24, D01));
+        let actual = super::format_message(id, Step::Download, &state);
+        assert_eq!(actual.as_deref(), expected);
+    }
+
+    #[test_case(0, "                   |   42 ms")]
+    #[test_case(1, "                   /   42 ms")]
+    #[test_case(5, "                   /   42 ms")]
+    fn format_with_ascii_spinner(ticks: usize, expected: &str) {
+        let config = UiConfig {
+            spinners: ASCII_SPINNERS,
+            ..UiConfig::default()
+        };
+
+        let now = Instant::now();
+        let state = State::Started(now - Duration::from_millis(42));
+        let spinner = config.spinner(ticks);
+        let actual = super::format_column_answer_and_time(
+            &state,
+            spinner,
+            now,
+            W_ANSWER_MIN,
+        );
         assert_eq!(&actual, expected);
     }
 
+    #[test]
+    fn format_table_header_default() {
+        let expected = concat!(
+            "Day ───┬ Fetch ──┬ Parse ──┬ ",
+            "Part 1 ──────────────────────┬ ",
+            "Part 2 ─────────────────────"
+        );
+        assert_eq!(format_table_header([W_ANSWER_MIN; 2]), expected);
+    }
+
+    #[test]
+    fn answer_widths_fit_wide_answers() {
+        let wide = "1234567890123456789012345";
+        let states = vec![
+            puzzle_state(
+                D01,
+                State::Skipped,
+                done_answer("42"),
+                State::Waiting,
+            ),
+            puzzle_state(
+                D02,
+                State::Skipped,
+                done_answer(wide),
+                done_answer("short"),
+            ),
+        ];
+
+        let widths = compute_answer_widths(&states, None, None);
+        assert_eq!(widths, [25, W_ANSWER_MIN]);
+
+        let header = format_table_header(widths);
+        let row = format_column_answer_and_time(
+            &states[1].p1,
+            "",
+            Instant::now(),
+            widths[0],
+        );
+        assert_eq!(row, format!("{wide}   42 ms"));
+
+        // The header cell includes the space before the column separator.
+        let header_cell = header.split('┬').nth(3).unwrap();
+        assert_eq!(header_cell.chars().count(), 1 + row.len() + 1);
+    }
+
+    #[test]
+    fn answer_widths_truncate_wide_answers() {
+        let wide = "1234567890123456789012345";
+        let states = vec![
+            puzzle_state(
+                D01,
+                State::Skipped,
+                done_answer("42"),
+                done_answer("äöüäöüäöü"),
+            ),
+            puzzle_state(
+                D02,
+                State::Skipped,
+                done_answer(wide),
+                done_answer("short"),
+            ),
+        ];
+
+        let widths = compute_answer_widths(&states, Some(8), None);
+        assert_eq!(widths, [8, 8]);
+
+        let format =
+            |state| format_column_answer_and_time(state, "", Instant::now(), 8);
+        assert_eq!(format(&states[0].p1), "      42   42 ms");
+        assert_eq!(format(&states[0].p2), "äöüäöüä…   42 ms");
+        assert_eq!(format(&states[1].p1), "1234567…   42 ms");
+        assert_eq!(format(&states[1].p2), "   short   42 ms");
+
+        assert_eq!(format_truncated_answers(&states, widths), vec![
+            String::from("y24d01p2: äöüäöüäöü"),
+            format!("y24d02p1: {wide}"),
+        ]);
+    }
+
+    #[test]
+    fn answer_widths_fixed() {
+        let states = vec![
+            puzzle_state(
+                D01,
+                State::Skipped,
+                done_answer("42"),
+                done_answer("1234567890"),
+            ),
+            puzzle_state(
+                D02,
+                State::Skipped,
+                done_answer("1337"),
+                State::Waiting,
+            ),
+        ];
+
+        let widths = compute_answer_widths(&states, Some(4), Some(12));
+        assert_eq!(widths, [12, 12]);
+
+        let format = |state| {
+            format_column_answer_and_time(state, "", Instant::now(), widths[0])
+        };
+        assert_eq!(format(&states[0].p1), "          42   42 ms");
+        assert_eq!(format(&states[0].p2), "  1234567890   42 ms");
+        assert_eq!(format(&states[1].p1), "        1337   42 ms");
+
+        assert!(format_truncated_answers(&states, widths).is_empty());
+    }
+
+    #[test_case("42", "        42"; "number")]
+    #[test_case("1234567890", "1234567890"; "number exact fit")]
+    #[test_case("4,6,3,5", "4,6,3,5   "; "comma-separated")]
+    #[test_case("ABC", "ABC       "; "text")]
+    #[test_case("ABCDEFGHIJKL", "ABCDEFGHI…"; "text truncated")]
+    fn align_answer_plain(answer: &str, expected: &str) {
+        assert_eq!(super::align_answer_plain(answer, 10), expected);
+    }
+
+    #[test_case(9, 1, 0, "9 solved, 1 failed"; "no skipped downloads")]
+    #[test_case(2, 0, 1, "2 solved, 0 failed, 1 download skipped"; "one")]
+    #[test_case(0, 0, 3, "0 solved, 0 failed, 3 downloads skipped"; "many")]
+    fn display_run_report(
+        solved: usize,
+        failed: usize,
+        skipped_downloads: usize,
+        expected: &str,
+    ) {
+        let report = RunReport {
+            solved,
+            failed,
+            skipped_downloads,
+        };
+        assert_eq!(report.to_string(), expected);
+    }
+
+    #[test_case(Theme::Mono, Step::Download, false, None)]
+    #[test_case(Theme::Mono, Step::Preproc, true, None)]
+    #[test_case(Theme::Mono, Step::Part1, false, None)]
+    #[test_case(Theme::Mono, Step::Part2, true, None)]
+    #[test_case(Theme::Dark, Step::Download, false, Some(Color::Gray))]
+    #[test_case(Theme::Dark, Step::Preproc, true, Some(Color::LightRed))]
+    #[test_case(Theme::Dark, Step::Part1, false, Some(Color::LightGreen))]
+    #[test_case(Theme::Dark, Step::Part2, true, Some(Color::LightRed))]
+    #[test_case(Theme::Light, Step::Download, false, Some(Color::DarkGray))]
+    #[test_case(Theme::Light, Step::Preproc, true, Some(Color::Red))]
+    #[test_case(Theme::Light, Step::Part1, false, Some(Color::Blue))]
+    #[test_case(Theme::Light, Step::Part2, true, Some(Color::Red))]
+    fn theme_style(
+        theme: Theme,
+        step: Step,
+        is_error: bool,
+        fg: Option<Color>,
+    ) {
+        let expected = match fg {
+            Some(color) => Style::default().fg(color),
+            None => Style::default(),
+        };
+
+        assert_eq!(theme.style(step, is_error), expected);
+    }
+
+    #[test_case(30, Some(50), Some(20), Some(30.0); "Both parts")]
+    #[test_case(10, Some(30), None, Some(25.0); "First part only")]
+    #[test_case(10, None, Some(10), Some(50.0); "Second part only")]
+    #[test_case(10, None, None, None; "No parts solved")]
+    #[test_case(0, Some(0), Some(0), None; "Zero time")]
+    fn parse_time_percentage(
+        parse_ms: u64,
+        p1_ms: Option<u64>,
+        p2_ms: Option<u64>,
+        expected: Option<f64>,
+    ) {
+        let done = |ms| State::Done(Duration::from_millis(ms), Ok(None));
+        let state = PuzzleState {
+            id: "y21d01".parse().unwrap(),
+            pd: State::Skipped,
+            p0: done(parse_ms),
+            p1: p1_ms
+                .map(done)
+                .unwrap_or(State::Skipped),
+            p2: p2_ms
+                .map(done)
+                .unwrap_or(State::Skipped),
+        };
+
+        let actual = state.parse_time_percentage();
+        match (actual, expected) {
+            (Some(actual), Some(expected)) => {
+                assert!((actual - expected).abs() < 1e-9, "{actual}");
+            }
+            (actual, expected) => assert_eq!(actual, expected),
+        }
+    }
+
+    #[test_case(SummaryOrder::Id, &[D01, D02, D03, D04])]
+    #[test_case(SummaryOrder::Time, &[D03, D01, D04, D02])]
+    fn sort_states(order: SummaryOrder, expected: &[Day]) {
+        let done = |ms| State::Done(Duration::from_millis(ms), Ok(None));
+        let states = [
+            puzzle_state(D01, done(10), done(20), done(30)),
+            puzzle_state(D02, done(5), State::Waiting, State::Skipped),
+            puzzle_state(D03, done(1), done(2), done(100)),
+            puzzle_state(
+                D04,
+                done(10),
+                done(10),
+                State::Done(Duration::from_millis(20), Err(err!("Mock error"))),
+            ),
+        ];
+
+        let days = super::sort_states(&states, order)
+            .into_iter()
+            .map(|&PuzzleState { id: Id((_, d)), .. }| d)
+            .collect::<Vec<_>>();
+        assert_eq!(days, expected);
+    }
+
+    #[test]
+    fn parse_time_percentage_requires_successful_parsing() {
+        let t = Duration::from_millis(10);
+        let state = PuzzleState {
+            id: "y21d01".parse().unwrap(),
+            pd: State::Skipped,
+            p0: State::Done(t, Err(err!("Mock error"))),
+            p1: State::Done(t, Ok(None)),
+            p2: State::Done(t, Ok(None)),
+        };
+
+        assert_eq!(state.parse_time_percentage(), None);
+    }
+
+    #[test_case("   0 B ", 0)]
+    #[test_case("9999 B ", 9_999)]
+    #[test_case("  10 KB", 10_000)]
+    #[test_case("  12 KB", 12_345)]
+    #[test_case("9999 KB", 9_999_999)]
+    #[test_case("  10 MB", 10_000_000)]
+    #[test_case("1234 MB", 1_234_567_890)]
+    fn format_bytes(expected: &str, bytes: usize) {
+        assert_eq!(super::format_bytes(bytes), expected);
+    }
+
     #[test_case("   0 µs", 0)]
     #[test_case("1234 µs", 1_234; "1234 micros")]
     #[test_case("1234 ms", 1_234_001)]
@@ -0,0 +1,181 @@
+use std::{
+    future::Future,
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
+use lazy_errors::{prelude::*, Result};
+use tokio::{task, time::interval};
+
+use crate::{
+    ident::{Day, Id, Year},
+    solver::{Event, Parts, Solver, State},
+};
+
+/// How often to check whether the personal puzzle input has changed.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Solves the puzzle of `solver` and writes the answers to `w`,
+/// then solves it again each time the modification time
+/// returned by `modified` changes, until `stop` completes.
+///
+/// Failing puzzle parts are written to `w` as well, so you can keep
+/// editing the input (or the solver) until the problem is fixed.
+/// Returns how often the puzzle was solved.
+pub async fn watch<W: Write>(
+    solver: &Solver,
+    parts: Parts,
+    mut modified: impl FnMut() -> Result<Option<SystemTime>>,
+    mut read_input: impl FnMut() -> Result<Option<String>>,
+    period: Duration,
+    stop: impl Future<Output = ()>,
+    mut w: W,
+) -> Result<usize> {
+    let id = Id((solver.year(), solver.day()));
+    let mut ticker = interval(period);
+    let mut last_modified = None;
+    let mut solved = 0;
+
+    tokio::pin!(stop);
+    loop {
+        tokio::select! {
+            () = &mut stop => return Ok(solved),
+            _ = ticker.tick() => {
+                let modified = Some(modified()?);
+                if modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+
+                let Some(input) = read_input()? else {
+                    writeln!(w, "{id}: Waiting for personal puzzle input")
+                        .or_wrap()?;
+                    continue;
+                };
+
+                if solved > 0 {
+                    writeln!(w, "{id}: Input changed, solving again")
+                        .or_wrap()?;
+                }
+
+                let mut events = solve(solver, parts, input).await?;
+                events.sort_by_key(|event| event.step);
+                for line in events
+                    .iter()
+                    .filter_map(|event| format_event(id, event))
+                {
+                    writeln!(w, "{line}").or_wrap()?;
+                }
+
+                solved += 1;
+            }
+        }
+    }
+}
+
+async fn solve(
+    solver: &Solver,
+    parts: Parts,
+    input: String,
+) -> Result<Vec<Event>> {
+    let solver = solver.clone();
+    task::spawn_blocking(move || solver.run_blocking(parts, &input))
+        .await
+        .or_wrap_with(|| "Failed to wait for solver")?
+}
+
+/// Formats the answer or error of a [`Step`](crate::solver::Step)
+/// that is [`State::Done`].
+fn format_event(id: Id<(Year, Day)>, event: &Event) -> Option<String> {
+    let step = event.step;
+    match &event.state {
+        State::Done(_, Ok(Some(answer))) => {
+            Some(format!("{id} {step}: {answer}"))
+        }
+        State::Done(_, Err(e)) => {
+            Some(format!("ERROR: Failed to {}: {e}", step.describe(id)))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::Notify;
+
+    use crate::{
+        ident::{day::*, year::*},
+        solver,
+    };
+
+    use super::*;
+
+    fn mock_len(input: &str) -> Result<usize> {
+        Ok(input.len())
+    }
+
+    fn mock_err(_: &str) -> Result<usize> {
+        Err(err!("Mock error"))
+    }
+
+    #[tokio::test]
+    async fn watch_solves_again_when_input_changed() -> Result<()> {
+        let solver = solver!(Y21, D01, mock_len, mock_err);
+
+        let t0 = SystemTime::UNIX_EPOCH;
+        let t1 = t0 + Duration::from_secs(1);
+        let mut mtimes = vec![None, Some(t0), Some(t0), Some(t1), Some(t1)];
+        mtimes.reverse();
+
+        let mut inputs = vec![None, Some("42"), Some("1337")];
+        inputs.reverse();
+
+        let done = Arc::new(Notify::new());
+
+        let modified = {
+            let done = done.clone();
+            move || {
+                if mtimes.len() == 1 {
+                    done.notify_one();
+                    return Ok(mtimes[0]);
+                }
+                Ok(mtimes.pop().unwrap())
+            }
+        };
+
+        let read_input = move || {
+            let input = inputs
+                .pop()
+                .ok_or_else(|| err!("Read input too often"))?;
+            Ok(input.map(String::from))
+        };
+
+        let mut output = vec![];
+        let solved = watch(
+            &solver,
+            Parts::Both,
+            modified,
+            read_input,
+            Duration::from_millis(1),
+            done.notified(),
+            &mut output,
+        )
+        .await?;
+
+        assert_eq!(solved, 2);
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().collect::<Vec<_>>(), vec![
+            "y21d01: Waiting for personal puzzle input",
+            "y21d01 part 1: 2",
+            "ERROR: Failed to solve y21d01p2: Mock error",
+            "y21d01: Input changed, solving again",
+            "y21d01 part 1: 4",
+            "ERROR: Failed to solve y21d01p2: Mock error",
+        ]);
+
+        Ok(())
+    }
+}